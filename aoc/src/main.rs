@@ -0,0 +1,133 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use common::{
+    Cli, CliError, Day, FileConfig, InputSource, Options, bench_day, reports_to_json, run_day, verify_day
+};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let cli = Cli::new("aoc run <day|all>");
+    let (day, options) = match parse_args(&cli, &args) {
+        Ok(command) => command,
+        Err(CliError::Help) => {
+            println!("{}", cli.usage());
+            process::exit(0);
+        },
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli.usage());
+            process::exit(2);
+        }
+    };
+
+    let days = days();
+    let selected: Vec<&Day> = match day {
+        Some(n) => match days.iter().find(|d| d.number == n) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {} is not solved yet", n);
+                process::exit(2);
+            }
+        },
+        None => days.iter().collect()
+    };
+
+    let mut failed = false;
+    let mut bench_reports = Vec::new();
+    let mut reports = Vec::new();
+    for day in selected {
+        let config = FileConfig::default(day.base_path);
+        if options.verify {
+            match verify_day(day, &config, &options.parts()) {
+                Ok(verification) => {
+                    println!("{}", verification);
+                    failed |= !verification.is_success();
+                },
+                Err(e) => {
+                    eprintln!("Day {} verification failed: {}", day.number, e);
+                    failed = true;
+                }
+            }
+            continue;
+        }
+
+        if let Some(iterations) = options.bench {
+            let report = options.read_input(&config)
+                .map_err(|e| e.into())
+                .and_then(|input| bench_day(day, &input, &options.parts(), iterations));
+            match report {
+                Ok(report) => {
+                    println!("{}", report);
+                    bench_reports.push(report);
+                },
+                Err(e) => {
+                    eprintln!("Day {} benchmark failed: {}", day.number, e);
+                    failed = true;
+                }
+            }
+            continue;
+        }
+
+        let report = options.read_input(&config)
+            .map_err(|e| e.into())
+            .and_then(|input| run_day(day, &input, &options.parts()));
+
+        match report {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                eprintln!("Day {} failed: {}", day.number, e);
+                failed = true;
+            }
+        }
+    }
+
+    if !reports.is_empty() {
+        options.print_reports(&reports);
+    }
+
+    if let Some(path) = &options.bench_json {
+        if let Err(e) = fs::write(path, reports_to_json(&bench_reports)) {
+            eprintln!("Unable to write {}: {}", path, e);
+            failed = true;
+        }
+    }
+
+    if failed { process::exit(1) }
+}
+
+/// Registered day solutions
+fn days() -> Vec<Day> {
+    vec![day_1::day(), day_2::day(), day_3::day(), day_4::day()]
+}
+
+/// Parse `run <day|all> [OPTIONS]`, day is `None` when running all days
+fn parse_args(cli: &Cli, args: &[String]) -> Result<(Option<u32>, Options), CliError> {
+    let mut args = args.iter();
+    match args.next().map(String::as_str) {
+        Some("run") => (),
+        Some("--help") | Some("-h") => return Err(CliError::Help),
+        Some(cmd) => return Err(CliError::UnknownOption(cmd.to_string())),
+        None => return Err(CliError::MissingValue("run".to_string()))
+    }
+
+    let day = match args.next().map(String::as_str) {
+        Some("all") => None,
+        Some(n) => Some(n.parse().map_err(|_| CliError::InvalidValue {
+            option: "run".to_string(),
+            value: n.to_string()
+        })?),
+        None => return Err(CliError::MissingValue("run".to_string()))
+    };
+
+    let options = cli.parse(args)?;
+    if day.is_none() {
+        match options.input {
+            InputSource::Path(_) => return Err(CliError::Conflict("all".to_string(), "--input".to_string())),
+            InputSource::Stdin => return Err(CliError::Conflict("all".to_string(), "--stdin".to_string())),
+            _ => ()
+        }
+    }
+
+    Ok((day, options))
+}
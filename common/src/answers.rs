@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::process;
+
+use crate::{Day, FileConfig, Part, run_day};
+
+/// Input an expected answer belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnswerInput {
+    Example,
+    Input
+}
+
+impl AnswerInput {
+    pub const ALL: [AnswerInput; 2] = [AnswerInput::Example, AnswerInput::Input];
+
+    fn from_section(section: &str) -> Option<AnswerInput> {
+        match section {
+            "example" => Some(AnswerInput::Example),
+            "input" => Some(AnswerInput::Input),
+            _ => None
+        }
+    }
+
+    /// Input file path of the day
+    pub fn path<'a>(&self, config: &'a FileConfig) -> &'a str {
+        match self {
+            AnswerInput::Example => &config.test_file,
+            AnswerInput::Input => &config.prod_file
+        }
+    }
+}
+
+impl Display for AnswerInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerInput::Example => write!(f, "example"),
+            AnswerInput::Input => write!(f, "input")
+        }
+    }
+}
+
+/// Error raised while parsing an answers file
+#[derive(Debug, PartialEq, Eq)]
+pub struct AnswersError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AnswersError {}
+
+/// Expected answers of a day
+///
+/// Answers are stored in a small TOML subset, one section per input:
+///
+/// ```toml
+/// [example]
+/// part_one = 7
+/// part_two = "5"
+///
+/// [input]
+/// part_one = 1692
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    expected: HashMap<(AnswerInput, Part), String>
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, AnswersError> {
+        let mut answers = Answers::default();
+        let mut section: Option<AnswerInput> = None;
+        for (idx, line) in text.lines().enumerate() {
+            let error = |message: String| AnswersError { line: idx + 1, message };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(AnswerInput::from_section(name.trim())
+                    .ok_or_else(|| error(format!("unknown section '{}'", name)))?);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| error(format!("expected 'key = value', found '{}'", line)))?;
+            let part = match key.trim() {
+                "part_one" => Part::One,
+                "part_two" => Part::Two,
+                unk => return Err(error(format!("unknown key '{}'", unk)))
+            };
+            let input = section.ok_or_else(|| error("answer outside of a section".to_string()))?;
+
+            answers.expected.insert((input, part), parse_value(value.trim()).map_err(error)?);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn load(path: &str) -> Result<Answers, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("unable to open {}: {}", path, e))?;
+
+        Answers::parse(&text).map_err(|e| format!("{}: {}", path, e).into())
+    }
+
+    pub fn expected(&self, input: AnswerInput, part: Part) -> Option<&str> {
+        self.expected.get(&(input, part)).map(String::as_str)
+    }
+}
+
+/// Quoted strings and bare integers are supported
+fn parse_value(value: &str) -> Result<String, String> {
+    if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        return Ok(text.to_string());
+    }
+
+    match value.parse::<i64>() {
+        Ok(num) => Ok(num.to_string()),
+        Err(_) => Err(format!("invalid value '{}', expected string or integer", value))
+    }
+}
+
+/// Comparison of an answer with the expected one
+pub struct AnswerCheck {
+    pub input: AnswerInput,
+    pub part: Part,
+    /// `None` when the answers file has no value for this part
+    pub expected: Option<String>,
+    pub actual: String
+}
+
+impl AnswerCheck {
+    /// Whether the expected answer is known and equal to the actual one
+    pub fn is_match(&self) -> bool {
+        self.expected.as_deref() == Some(self.actual.as_str())
+    }
+}
+
+impl Display for AnswerCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} part {}: ", self.input, self.part)?;
+        match &self.expected {
+            None => write!(f, "MISSING expected answer ({})", self.actual),
+            Some(_) if self.is_match() => write!(f, "ok ({})", self.actual),
+            Some(expected) => write!(f, "MISMATCH\n    - expected: {}\n    + actual:   {}", expected, self.actual)
+        }
+    }
+}
+
+/// Result of checking every answer of a day
+pub struct Verification {
+    pub day: u32,
+    pub checks: Vec<AnswerCheck>
+}
+
+impl Verification {
+    pub fn is_success(&self) -> bool {
+        self.checks.iter().all(AnswerCheck::is_match)
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let matched = self.checks.iter().filter(|c| c.is_match()).count();
+        write!(f, "Day {}: {}/{} answers verified", self.day, matched, self.checks.len())?;
+        for check in &self.checks {
+            write!(f, "\n  {}", check)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run `parts` of a day over its example and puzzle inputs comparing with its answers file
+pub fn verify_day(day: &Day, config: &FileConfig, parts: &[Part]) -> Result<Verification, Box<dyn Error>> {
+    let answers = Answers::load(&config.answers_file)?;
+
+    let mut checks = Vec::new();
+    for input in AnswerInput::ALL {
+        let path = input.path(config);
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("unable to open {}: {}", path, e))?;
+        let report = run_day(day, &text, parts)
+            .map_err(|e| format!("{}: {}", path, e))?;
+
+        checks.extend(report.parts.into_iter().map(|part| AnswerCheck {
+            input,
+            part: part.part,
+            expected: answers.expected(input, part.part).map(String::from),
+            actual: part.answer.to_string()
+        }));
+    }
+
+    Ok(Verification { day: day.number, checks })
+}
+
+/// Verify day answers printing the result, exits with non-zero status on any mismatch, missing answer or error
+pub fn verify_and_exit(day: &Day, config: &FileConfig, parts: &[Part]) -> ! {
+    match verify_day(day, config, parts) {
+        Ok(verification) => {
+            println!("{}", verification);
+            process::exit(if verification.is_success() { 0 } else { 1 });
+        },
+        Err(e) => {
+            eprintln!("Day {} verification failed: {}", day.number, e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Part;
+    use super::{AnswerCheck, AnswerInput, Answers, AnswersError, Verification};
+
+    #[test]
+    fn should_parse_answers() {
+        let answers = Answers::parse("# Day answers\n[example]\npart_one = 7\npart_two = \"abc\"\n\n[input]\npart_two = -3\n").unwrap();
+
+        assert_eq!(Some("7"), answers.expected(AnswerInput::Example, Part::One));
+        assert_eq!(Some("abc"), answers.expected(AnswerInput::Example, Part::Two));
+        assert_eq!(None, answers.expected(AnswerInput::Input, Part::One));
+        assert_eq!(Some("-3"), answers.expected(AnswerInput::Input, Part::Two));
+    }
+
+    #[test]
+    fn should_report_invalid_answers_file() {
+        let missing_section = Answers::parse("part_one = 1").unwrap_err();
+        let unknown_key = Answers::parse("[input]\npart_three = 1").unwrap_err();
+
+        assert_eq!(1, missing_section.line);
+        assert_eq!(AnswersError { line: 2, message: "unknown key 'part_three'".to_string() }, unknown_key);
+        assert!(Answers::parse("[input]\npart_one = abc").is_err());
+    }
+
+    #[test]
+    fn should_display_mismatch_diff() {
+        let check = AnswerCheck { input: AnswerInput::Input, part: Part::One, expected: Some("7".to_string()), actual: "8".to_string() };
+
+        assert!(!check.is_match());
+        assert_eq!("input part 1: MISMATCH\n    - expected: 7\n    + actual:   8", check.to_string());
+    }
+
+    #[test]
+    fn should_fail_on_missing_expected_answer() {
+        let missing = AnswerCheck { input: AnswerInput::Example, part: Part::Two, expected: None, actual: "5".to_string() };
+        let ok = AnswerCheck { input: AnswerInput::Example, part: Part::One, expected: Some("7".to_string()), actual: "7".to_string() };
+        let verification = Verification { day: 1, checks: vec![ok, missing] };
+
+        assert!(!verification.is_success());
+        assert_eq!(
+            "Day 1: 1/2 answers verified\n  example part 1: ok (7)\n  example part 2: MISSING expected answer (5)",
+            verification.to_string()
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::hint;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::json;
+use crate::{Day, FileConfig, Options, Part};
+
+/// Summary of the samples taken for a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration
+}
+
+impl Stats {
+    /// `None` when there are no samples
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() { return None }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2 } else { sorted[mid] };
+        let mean = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+
+        Some(Stats { min: sorted[0], median, mean, max: sorted[sorted.len() - 1] })
+    }
+}
+
+/// Timing statistics of a solution step
+pub struct StepStats {
+    /// `parse`, `part_one` or `part_two`
+    pub step: &'static str,
+    pub stats: Stats
+}
+
+/// Timing statistics of a day over several iterations
+pub struct BenchReport {
+    pub day: u32,
+    pub title: &'static str,
+    pub iterations: usize,
+    pub steps: Vec<StepStats>
+}
+
+impl BenchReport {
+    pub fn to_json(&self) -> String {
+        let steps: Vec<String> = self.steps.iter()
+            .map(|s| format!(
+                "{{\"step\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"max_ns\":{}}}",
+                json::quote(s.step),
+                s.stats.min.as_nanos(),
+                s.stats.median.as_nanos(),
+                s.stats.mean.as_nanos(),
+                s.stats.max.as_nanos()
+            ))
+            .collect();
+
+        format!(
+            "{{\"day\":{},\"title\":{},\"iterations\":{},\"steps\":[{}]}}",
+            self.day,
+            json::quote(self.title),
+            self.iterations,
+            steps.join(",")
+        )
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {}: {} ({} iterations)", self.day, self.title, self.iterations)?;
+        write!(f, "\n  {:<10} {:>12} {:>12} {:>12} {:>12}", "step", "min", "median", "mean", "max")?;
+        for step in &self.steps {
+            write!(
+                f,
+                "\n  {:<10} {:>12} {:>12} {:>12} {:>12}",
+                step.step,
+                format!("{:.2?}", step.stats.min),
+                format!("{:.2?}", step.stats.median),
+                format!("{:.2?}", step.stats.mean),
+                format!("{:.2?}", step.stats.max)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// JSON array with the reports of several days
+pub fn reports_to_json(reports: &[BenchReport]) -> String {
+    let reports: Vec<String> = reports.iter().map(BenchReport::to_json).collect();
+    format!("[{}]", reports.join(","))
+}
+
+/// Time parse and `parts` of a day separately over `iterations` runs
+///
+/// An additional warm up run is done before taking samples.
+pub fn bench_day(day: &Day, input: &str, parts: &[Part], iterations: usize) -> Result<BenchReport, Box<dyn Error>> {
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut part_samples: Vec<Vec<Duration>> = vec![Vec::with_capacity(iterations); parts.len()];
+    for iteration in 0..=iterations {
+        let start = Instant::now();
+        let solution = day.parse(input)?;
+        let parse_time = start.elapsed();
+
+        let part_times: Vec<Duration> = parts.iter()
+            .map(|part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => solution.part_one(),
+                    Part::Two => solution.part_two()
+                }?;
+                let time = start.elapsed();
+                hint::black_box(answer);
+
+                Ok(time)
+            })
+            .collect::<Result<_, Box<dyn Error>>>()?;
+
+        // First run is warm up
+        if iteration == 0 { continue }
+
+        parse_samples.push(parse_time);
+        part_samples.iter_mut().zip(part_times).for_each(|(samples, time)| samples.push(time));
+    }
+
+    let mut steps = Vec::new();
+    if let Some(stats) = Stats::from_samples(&parse_samples) {
+        steps.push(StepStats { step: "parse", stats });
+    }
+    for (part, samples) in parts.iter().zip(part_samples) {
+        let step = match part {
+            Part::One => "part_one",
+            Part::Two => "part_two"
+        };
+        if let Some(stats) = Stats::from_samples(&samples) {
+            steps.push(StepStats { step, stats });
+        }
+    }
+
+    Ok(BenchReport { day: day.number, title: day.title, iterations, steps })
+}
+
+/// Benchmark day with the selected input printing the report, exits with non-zero status on error
+///
+/// The JSON report is also written when `--bench-json` was given.
+pub fn bench_and_exit(day: &Day, options: &Options, config: &FileConfig, iterations: usize) -> ! {
+    let report = options.read_input(config)
+        .map_err(|e| e.into())
+        .and_then(|input| bench_day(day, &input, &options.parts(), iterations));
+
+    match report {
+        Ok(report) => {
+            println!("{}", report);
+            if let Some(path) = &options.bench_json {
+                if let Err(e) = fs::write(path, reports_to_json(&[report])) {
+                    eprintln!("Unable to write {}: {}", path, e);
+                    process::exit(1);
+                }
+            }
+            process::exit(0);
+        },
+        Err(e) => {
+            eprintln!("Day {} benchmark failed: {}", day.number, e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn should_compute_stats() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].iter().map(|ms| Duration::from_millis(*ms)).collect();
+
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(2500), stats.median);
+        assert_eq!(Duration::from_micros(2500), stats.mean);
+        assert_eq!(Duration::from_millis(4), stats.max);
+    }
+
+    #[test]
+    fn should_return_no_stats_without_samples() {
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+}
@@ -0,0 +1,136 @@
+use std::str::FromStr;
+
+use crate::{InputError, try_load_data};
+
+/// Group of consecutive non blank lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// 1-based line number of the first line in the block
+    pub line: usize,
+    pub lines: Vec<String>
+}
+
+impl Block {
+    /// Block lines joined by line breaks
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Parse block text into `T`, reporting errors from `path`
+    pub fn parse<T: FromStr>(&self, path: &str) -> Result<T, InputError<T::Err>> {
+        self.parse_with(path, |block| block.text().parse())
+    }
+
+    /// Parse block using `parser`, reporting errors from `path`
+    pub fn parse_with<T, E, F>(&self, path: &str, parser: F) -> Result<T, InputError<E>>
+        where F: FnOnce(&Block) -> Result<T, E>
+    {
+        parser(self).map_err(|source| InputError::Parse {
+            path: path.to_string(),
+            line: self.line,
+            text: self.text(),
+            source
+        })
+    }
+}
+
+/// Iterator splitting lines into blank line separated blocks, see [`split_blocks`]
+pub struct Blocks<I> {
+    lines: I,
+    line_n: usize
+}
+
+impl<I: Iterator<Item = String>> Iterator for Blocks<I> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block: Option<Block> = None;
+        for line in self.lines.by_ref() {
+            self.line_n += 1;
+            if line.trim().is_empty() {
+                if block.is_some() { break }
+                continue;
+            }
+
+            block.get_or_insert_with(|| Block { line: self.line_n, lines: Vec::new() })
+                .lines
+                .push(line);
+        }
+
+        // Last block is returned even if input does not end with a blank line
+        block
+    }
+}
+
+/// Split lines into blocks separated by one or more blank lines
+///
+/// Leading, trailing and repeated blank lines never produce empty blocks.
+pub fn split_blocks<I: IntoIterator<Item = String>>(lines: I) -> Blocks<I::IntoIter> {
+    Blocks { lines: lines.into_iter(), line_n: 0 }
+}
+
+/// Load blank line separated blocks of a file
+pub fn try_load_blocks(path: &str) -> Result<Vec<Block>, InputError> {
+    Ok(split_blocks(try_load_data(path)?).collect())
+}
+
+/// Load blank line separated blocks of a file, returning first block apart as header
+///
+/// Header is empty when the file has no content.
+pub fn try_load_header_and_blocks(path: &str) -> Result<(Block, Vec<Block>), InputError> {
+    let mut blocks = split_blocks(try_load_data(path)?);
+    let header = blocks.next().unwrap_or(Block { line: 1, lines: Vec::new() });
+
+    Ok((header, blocks.collect()))
+}
+
+/// Load blank line separated blocks of a file parsing each one into `T`
+pub fn try_load_blocks_with_type<T: FromStr>(path: &str) -> Result<Vec<T>, InputError<T::Err>> {
+    try_load_blocks_with(path, |block| block.text().parse())
+}
+
+/// Load blank line separated blocks of a file parsing each one with `parser`
+pub fn try_load_blocks_with<T, E, F>(path: &str, mut parser: F) -> Result<Vec<T>, InputError<E>>
+    where F: FnMut(&Block) -> Result<T, E>
+{
+    let blocks = try_load_blocks(path).map_err(InputError::into_parse_error)?;
+
+    blocks.iter()
+        .map(|block| block.parse_with(path, &mut parser))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Block, split_blocks};
+
+    #[test]
+    fn should_split_blocks_on_blank_lines() {
+        let blocks: Vec<Block> = split_blocks(to_lines("a\nb\n\nc\n\n\nd\ne")).collect();
+
+        assert_eq!(3, blocks.len());
+        assert_eq!(Block { line: 1, lines: vec!["a".to_string(), "b".to_string()] }, blocks[0]);
+        assert_eq!(Block { line: 4, lines: vec!["c".to_string()] }, blocks[1]);
+        assert_eq!(Block { line: 7, lines: vec!["d".to_string(), "e".to_string()] }, blocks[2]);
+    }
+
+    #[test]
+    fn should_ignore_leading_and_trailing_blank_lines() {
+        let blocks: Vec<Block> = split_blocks(to_lines("\n\na\n\n")).collect();
+
+        assert_eq!(vec![Block { line: 3, lines: vec!["a".to_string()] }], blocks);
+    }
+
+    #[test]
+    fn should_report_block_start_on_parse_error() {
+        let blocks: Vec<Block> = split_blocks(to_lines("1\n\nx\ny")).collect();
+
+        let err = blocks[1].parse::<u32>("input").unwrap_err();
+
+        assert_eq!("input:3: unable to parse 'x\ny': invalid digit found in string", err.to_string());
+    }
+
+    fn to_lines(text: &str) -> Vec<String> {
+        text.split('\n').map(String::from).collect()
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::process;
+
+use crate::{
+    Day, DayReport, FileConfig, Format, InputError, InputReader, Lines, Part, STDIN_NAME, STDIN_PATH, bench_and_exit,
+    lines, render_reports, verify_and_exit
+};
+
+/// Where puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// Day puzzle input file
+    Default,
+    /// Day example input file
+    Example,
+    Path(String),
+    Stdin
+}
+
+/// Error raised while parsing command line arguments
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    /// `--help` was requested
+    Help,
+    UnknownOption(String),
+    MissingValue(String),
+    /// Flag given an inline value, e.g. `--quiet=yes`
+    UnexpectedValue(String),
+    InvalidValue { option: String, value: String },
+    /// Options that can not be used together
+    Conflict(String, String),
+    /// First option can only be used along with the second one
+    Requires(String, String)
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Help => write!(f, "help requested"),
+            CliError::UnknownOption(opt) => write!(f, "unknown option '{}'", opt),
+            CliError::MissingValue(opt) => write!(f, "option '{}' expects a value", opt),
+            CliError::UnexpectedValue(opt) => write!(f, "option '{}' does not take a value", opt),
+            CliError::InvalidValue { option, value } =>
+                write!(f, "invalid value '{}' for option '{}'", value, option),
+            CliError::Conflict(a, b) => write!(f, "options '{}' and '{}' can not be used together", a, b),
+            CliError::Requires(a, b) => write!(f, "option '{}' requires '{}'", a, b)
+        }
+    }
+}
+
+impl Error for CliError {}
+
+struct ExtraOption {
+    name: &'static str,
+    value_name: Option<&'static str>,
+    help: &'static str
+}
+
+/// Command line parser shared by all day binaries
+///
+/// Common options are always available, days can register extra flags and valued options.
+pub struct Cli {
+    name: String,
+    extras: Vec<ExtraOption>
+}
+
+impl Cli {
+    pub fn new(name: &str) -> Cli {
+        Cli { name: name.to_string(), extras: Vec::new() }
+    }
+
+    /// Register boolean flag, e.g. `--stats`
+    pub fn flag(mut self, name: &'static str, help: &'static str) -> Cli {
+        self.extras.push(ExtraOption { name, value_name: None, help });
+        self
+    }
+
+    /// Register option taking a value, e.g. `--windows <list>`
+    pub fn option(mut self, name: &'static str, value_name: &'static str, help: &'static str) -> Cli {
+        self.extras.push(ExtraOption { name, value_name: Some(value_name), help });
+        self
+    }
+
+    pub fn usage(&self) -> String {
+        let mut lines = vec![
+            ("--input <path>".to_string(), "Read puzzle input from <path>, '-' reads standard input"),
+            ("--stdin".to_string(), "Read puzzle input from standard input"),
+            ("--example".to_string(), "Use the example input instead of the puzzle input"),
+            ("--part <1|2>".to_string(), "Only run the given part"),
+            ("--quiet".to_string(), "Only print answers"),
+            ("--format <text|json|tsv>".to_string(), "Answers output format, defaults to text"),
+            ("--verify".to_string(), "Check answers of example and puzzle inputs against resources/answers.toml"),
+            ("--bench <iterations>".to_string(), "Time parse and each part over <iterations> runs"),
+            ("--bench-json <path>".to_string(), "Also write benchmark report as JSON to <path>, requires --bench"),
+        ];
+        for extra in &self.extras {
+            let usage = match extra.value_name {
+                Some(value_name) => format!("{} <{}>", extra.name, value_name),
+                None => extra.name.to_string()
+            };
+            lines.push((usage, extra.help));
+        }
+        lines.push(("--help".to_string(), "Print this message"));
+
+        let width = lines.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+        let options: Vec<String> = lines.iter()
+            .map(|(usage, help)| format!("  {:<width$}  {}", usage, help, width = width))
+            .collect();
+
+        format!("Usage: {} [OPTIONS]\n\nOptions:\n{}", self.name, options.join("\n"))
+    }
+
+    /// Parse arguments, excluding the program name
+    pub fn parse<I, S>(&self, args: I) -> Result<Options, CliError>
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let mut options = Options::default();
+        let mut input_option: Option<&str> = None;
+        let mut set_input = |option: &'static str, source: InputSource, options: &mut Options| {
+            if let Some(previous) = input_option {
+                return Err(CliError::Conflict(previous.to_string(), option.to_string()));
+            }
+            input_option = Some(option);
+            options.input = source;
+            Ok(())
+        };
+
+        let args: Vec<String> = args.into_iter().map(|a| a.as_ref().to_string()).collect();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None)
+            };
+            let value = |args: &mut dyn Iterator<Item = String>| {
+                inline_value.clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::MissingValue(name.clone()))
+            };
+            let no_value = || match inline_value {
+                Some(_) => Err(CliError::UnexpectedValue(name.clone())),
+                None => Ok(())
+            };
+
+            match name.as_str() {
+                "--help" | "-h" => {
+                    no_value()?;
+                    return Err(CliError::Help);
+                },
+                "--input" => {
+                    let path = value(&mut args)?;
+                    let source = if path == STDIN_PATH { InputSource::Stdin } else { InputSource::Path(path) };
+                    set_input("--input", source, &mut options)?;
+                },
+                "--stdin" => {
+                    no_value()?;
+                    set_input("--stdin", InputSource::Stdin, &mut options)?;
+                },
+                "--example" => {
+                    no_value()?;
+                    set_input("--example", InputSource::Example, &mut options)?;
+                },
+                "--part" => {
+                    let part = value(&mut args)?;
+                    options.part = Some(match part.as_str() {
+                        "1" => Part::One,
+                        "2" => Part::Two,
+                        _ => return Err(CliError::InvalidValue { option: name, value: part })
+                    });
+                },
+                "--quiet" | "-q" => {
+                    no_value()?;
+                    options.quiet = true;
+                },
+                "--format" => {
+                    let format = value(&mut args)?;
+                    options.format = format.parse()
+                        .map_err(|_| CliError::InvalidValue { option: name, value: format })?;
+                },
+                "--verify" => {
+                    no_value()?;
+                    options.verify = true;
+                },
+                "--bench" => {
+                    let iterations = value(&mut args)?;
+                    options.bench = match iterations.parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(CliError::InvalidValue { option: name, value: iterations })
+                    };
+                },
+                "--bench-json" => options.bench_json = Some(value(&mut args)?),
+                _ => match self.extras.iter().find(|e| e.name == name) {
+                    Some(extra) if extra.value_name.is_some() => {
+                        let extra_value = value(&mut args)?;
+                        options.extras.insert(extra.name, Some(extra_value));
+                    },
+                    Some(extra) => {
+                        no_value()?;
+                        options.extras.insert(extra.name, None);
+                    },
+                    None => return Err(CliError::UnknownOption(arg))
+                }
+            }
+        }
+
+        if options.bench_json.is_some() && options.bench.is_none() {
+            return Err(CliError::Requires("--bench-json".to_string(), "--bench".to_string()));
+        }
+        if options.verify && options.bench.is_some() {
+            return Err(CliError::Conflict("--verify".to_string(), "--bench".to_string()));
+        }
+        // Verification always runs both the example and puzzle inputs
+        if let (true, Some(input)) = (options.verify, input_option) {
+            return Err(CliError::Conflict("--verify".to_string(), input.to_string()));
+        }
+
+        Ok(options)
+    }
+
+    /// Parse process arguments, printing usage and exiting on error or `--help`
+    pub fn parse_env(&self) -> Options {
+        match self.parse(env::args().skip(1)) {
+            Ok(options) => options,
+            Err(CliError::Help) => {
+                println!("{}", self.usage());
+                process::exit(0);
+            },
+            Err(e) => {
+                eprintln!("error: {}\n\n{}", e, self.usage());
+                process::exit(2);
+            }
+        }
+    }
+}
+
+/// Parsed command line options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub input: InputSource,
+    /// Part to run, `None` runs both
+    pub part: Option<Part>,
+    pub quiet: bool,
+    pub format: Format,
+    /// Compare answers with the expected ones instead of printing them
+    pub verify: bool,
+    /// Benchmark iterations, benchmarks instead of printing answers when set
+    pub bench: Option<usize>,
+    /// Path to write the benchmark JSON report to
+    pub bench_json: Option<String>,
+    extras: HashMap<&'static str, Option<String>>
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            input: InputSource::Default,
+            part: None,
+            quiet: false,
+            format: Format::Text,
+            verify: false,
+            bench: None,
+            bench_json: None,
+            extras: HashMap::new()
+        }
+    }
+}
+
+impl Options {
+    /// Run modes shared by all days (`--verify`, `--bench`), exiting when one of them was requested
+    pub fn run_shared_modes(&self, day: &Day, config: &FileConfig) {
+        if self.verify { verify_and_exit(day, config, &self.parts()) }
+        if let Some(iterations) = self.bench { bench_and_exit(day, self, config, iterations) }
+    }
+
+    /// Parts selected to run
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec()
+        }
+    }
+
+    pub fn runs(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Whether an extra flag or option was given
+    pub fn flag(&self, name: &str) -> bool {
+        self.extras.contains_key(name)
+    }
+
+    /// Value of an extra option
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.extras.get(name).and_then(|v| v.as_deref())
+    }
+
+    /// Input file path, `None` when reading from standard input
+    pub fn input_path<'a>(&'a self, config: &'a FileConfig) -> Option<&'a str> {
+        match &self.input {
+            InputSource::Default => Some(&config.prod_file),
+            InputSource::Example => Some(&config.test_file),
+            InputSource::Path(path) => Some(path),
+            InputSource::Stdin => None
+        }
+    }
+
+    /// Name of the input used in error messages
+    pub fn input_name<'a>(&'a self, config: &'a FileConfig) -> &'a str {
+        self.input_path(config).unwrap_or(STDIN_NAME)
+    }
+
+    /// Lazily iterate over trimmed input lines
+    pub fn lines(&self, config: &FileConfig) -> Result<Lines<InputReader>, InputError> {
+        lines(self.input_path(config).unwrap_or(STDIN_PATH))
+    }
+
+    /// Read whole input as trimmed lines joined by line breaks
+    pub fn read_input(&self, config: &FileConfig) -> Result<String, InputError> {
+        let lines = self.lines(config)?.collect::<Result<Vec<String>, _>>()?;
+
+        Ok(lines.join("\n"))
+    }
+
+    /// Print reports in the selected format, only answer values are printed in quiet text mode
+    pub fn print_reports(&self, reports: &[DayReport]) {
+        if self.quiet && self.format == Format::Text {
+            reports.iter()
+                .flat_map(|r| &r.parts)
+                .for_each(|p| println!("{}", p.answer));
+        } else {
+            println!("{}", render_reports(reports, self.format));
+        }
+    }
+
+    pub fn print_report(&self, report: &DayReport) {
+        self.print_reports(std::slice::from_ref(report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileConfig, Format, Part};
+    use super::{Cli, CliError, InputSource};
+
+    #[test]
+    fn should_parse_common_options() {
+        let options = Cli::new("day").parse(["--input", "data.txt", "--part", "2", "--quiet", "--format=json"]).unwrap();
+
+        assert_eq!(InputSource::Path("data.txt".to_string()), options.input);
+        assert_eq!(Some(Part::Two), options.part);
+        assert_eq!(vec![Part::Two], options.parts());
+        assert!(options.quiet);
+        assert_eq!(Format::Json, options.format);
+    }
+
+    #[test]
+    fn should_default_to_puzzle_input_and_both_parts() {
+        let options = Cli::new("day").parse(Vec::<String>::new()).unwrap();
+        let config = FileConfig::default("./day");
+
+        assert_eq!(Some("./day/resources/input_data.txt"), options.input_path(&config));
+        assert_eq!(vec![Part::One, Part::Two], options.parts());
+    }
+
+    #[test]
+    fn should_select_example_and_stdin() {
+        let config = FileConfig::default("./day");
+        let example = Cli::new("day").parse(["--example"]).unwrap();
+        let stdin = Cli::new("day").parse(["--stdin"]).unwrap();
+        let dash = Cli::new("day").parse(["--input", "-"]).unwrap();
+
+        assert_eq!(Some("./day/resources/min_input_data.txt"), example.input_path(&config));
+        assert_eq!(InputSource::Stdin, stdin.input);
+        assert_eq!(None, stdin.input_path(&config));
+        assert_eq!(InputSource::Stdin, dash.input);
+    }
+
+    #[test]
+    fn should_reject_invalid_arguments() {
+        let cli = Cli::new("day");
+
+        assert_eq!(Err(CliError::UnknownOption("--bogus".to_string())), cli.parse(["--bogus"]));
+        assert_eq!(Err(CliError::MissingValue("--input".to_string())), cli.parse(["--input"]));
+        assert!(matches!(cli.parse(["--part", "3"]), Err(CliError::InvalidValue { .. })));
+        assert!(matches!(cli.parse(["--stdin", "--example"]), Err(CliError::Conflict(..))));
+        assert!(matches!(cli.parse(["--bench", "0"]), Err(CliError::InvalidValue { .. })));
+        assert!(matches!(cli.parse(["--format", "xml"]), Err(CliError::InvalidValue { .. })));
+        assert_eq!(Err(CliError::UnknownOption("test".to_string())), cli.parse(["test"]));
+        assert_eq!(Err(CliError::UnexpectedValue("--quiet".to_string())), cli.parse(["--quiet=x"]));
+        assert_eq!(Err(CliError::UnexpectedValue("--stdin".to_string())), cli.parse(["--stdin=x"]));
+        assert_eq!(Err(CliError::Help), cli.parse(["--help"]));
+    }
+
+    #[test]
+    fn should_reject_mode_combinations() {
+        let cli = Cli::new("day");
+
+        assert_eq!(
+            Err(CliError::Requires("--bench-json".to_string(), "--bench".to_string())),
+            cli.parse(["--bench-json", "bench.json"])
+        );
+        assert_eq!(
+            Err(CliError::Conflict("--verify".to_string(), "--bench".to_string())),
+            cli.parse(["--bench", "3", "--verify"])
+        );
+        assert!(cli.parse(["--bench", "3", "--bench-json", "bench.json"]).is_ok());
+        assert_eq!(
+            Err(CliError::Conflict("--verify".to_string(), "--example".to_string())),
+            cli.parse(["--example", "--verify"])
+        );
+        assert!(matches!(cli.parse(["--verify", "--input", "data.txt"]), Err(CliError::Conflict(..))));
+    }
+
+    #[test]
+    fn should_parse_extra_options() {
+        let cli = Cli::new("day").flag("--stats", "Print stats").option("--windows", "list", "Window sizes");
+
+        let options = cli.parse(["--stats", "--windows", "1,3"]).unwrap();
+
+        assert!(options.flag("--stats"));
+        assert_eq!(Some("1,3"), options.value("--windows"));
+        assert!(cli.usage().contains("--windows <list>"));
+        assert_eq!(Err(CliError::UnexpectedValue("--stats".to_string())), cli.parse(["--stats=1"]));
+    }
+}
@@ -0,0 +1,383 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+const NEIGHBOUR_4_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const NEIGHBOUR_8_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, -1), (1, 0), (1, 1)
+];
+
+/// Error raised while building a grid
+///
+/// `E` is the error of the `FromStr` implementation used to parse cells.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError<E = Infallible> {
+    /// Row length differs from the first row length
+    Ragged { row: usize, expected: usize, found: usize },
+    /// Cell could not be parsed into the grid type
+    Parse { row: usize, col: usize, text: String, source: E }
+}
+
+impl<E: Display> Display for GridError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Ragged { row, expected, found } =>
+                write!(f, "row {} has {} cells, expected {}", row, found, expected),
+            GridError::Parse { row, col, text, source } =>
+                write!(f, "unable to parse cell ({}, {}) '{}': {}", row, col, text, source)
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for GridError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GridError::Ragged { .. } => None,
+            GridError::Parse { source, .. } => Some(source)
+        }
+    }
+}
+
+/// Rectangular grid stored in row-major order
+///
+/// Positions are `(row, col)` tuples starting by 0 from the top left cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize
+}
+
+impl<T> Grid<T> {
+    /// Create grid with all cells set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+        where T: Clone
+    {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+
+    /// Create grid from rows, all rows must have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        let mut cells = Vec::with_capacity(width * height);
+        for (row_n, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged { row: row_n, expected: width, found: row.len() });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid { cells, width, height })
+    }
+
+    /// Parse grid where cells are separated by any amount of whitespace
+    pub fn parse_whitespace(text: &str) -> Result<Grid<T>, GridError<T::Err>>
+        where T: FromStr
+    {
+        Grid::parse_rows(text, |line| line.split_whitespace().collect())
+    }
+
+    /// Parse grid where cells are separated by `separator`
+    pub fn parse_separated(text: &str, separator: char) -> Result<Grid<T>, GridError<T::Err>>
+        where T: FromStr
+    {
+        Grid::parse_rows(text, |line| line.split(separator).map(str::trim).collect())
+    }
+
+    /// Parse grid where each character is a cell
+    pub fn parse_chars(text: &str) -> Result<Grid<T>, GridError<T::Err>>
+        where T: FromStr
+    {
+        Grid::parse_rows(text, |line| {
+            line.char_indices()
+                .map(|(idx, c)| &line[idx..idx + c.len_utf8()])
+                .collect()
+        })
+    }
+
+    fn parse_rows<F>(text: &str, split: F) -> Result<Grid<T>, GridError<T::Err>>
+        where T: FromStr, F: Fn(&str) -> Vec<&str>
+    {
+        let mut rows = Vec::new();
+        for (row_n, line) in text.lines().map(str::trim).filter(|l| !l.is_empty()).enumerate() {
+            let row = split(line).into_iter()
+                .enumerate()
+                .map(|(col_n, cell)| cell.parse().map_err(|source| GridError::Parse {
+                    row: row_n,
+                    col: col_n,
+                    text: cell.to_string(),
+                    source
+                }))
+                .collect::<Result<Vec<T>, _>>()?;
+            rows.push(row);
+        }
+
+        Grid::from_rows(rows).map_err(|e| match e {
+            GridError::Ragged { row, expected, found } => GridError::Ragged { row, expected, found },
+            GridError::Parse { source, .. } => match source {}
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if !self.contains(row, col) { return None }
+
+        self.cells.get(row * self.width + col)
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if !self.contains(row, col) { return None }
+
+        self.cells.get_mut(row * self.width + col)
+    }
+
+    /// Replace cell value returning the previous one
+    ///
+    /// Returns `None`, leaving the grid untouched, when position is out of bounds.
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+        self.get_mut(row, col).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Get row values
+    ///
+    /// # Arguments
+    /// * `row` - Row index starting by 0
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row >= self.height { return None }
+
+        Some(&self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    /// Get column values from top to bottom
+    ///
+    /// # Arguments
+    /// * `col` - Col index starting by 0
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        if col >= self.width { return None }
+
+        Some(self.cells[col..].iter().step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.cells[col..].iter().step_by(self.width))
+    }
+
+    /// Values from top left cell going down to the right until an edge is reached
+    pub fn diagonal(&self) -> impl Iterator<Item = &T> {
+        (0..self.width.min(self.height)).map(move |i| &self.cells[i * self.width + i])
+    }
+
+    /// Values from top right cell going down to the left until an edge is reached
+    pub fn anti_diagonal(&self) -> impl Iterator<Item = &T> {
+        (0..self.width.min(self.height)).map(move |i| &self.cells[i * self.width + self.width - 1 - i])
+    }
+
+    /// Iterate cells with their positions in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(idx, it)| ((idx / width, idx % width), it))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.cells.iter_mut().enumerate().map(move |(idx, it)| ((idx / width, idx % width), it))
+    }
+
+    /// Positions of orthogonal neighbours inside the grid
+    pub fn neighbours_4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(row, col, &NEIGHBOUR_4_OFFSETS)
+    }
+
+    /// Positions of orthogonal and diagonal neighbours inside the grid
+    pub fn neighbours_8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(row, col, &NEIGHBOUR_8_OFFSETS)
+    }
+
+    fn neighbours(
+        &self,
+        row: usize,
+        col: usize,
+        offsets: &'static [(isize, isize)]
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |(row_off, col_off)| {
+            if !self.contains(row, col) { return None }
+
+            let n_row = row.checked_add_signed(*row_off)?;
+            let n_col = col.checked_add_signed(*col_off)?;
+            if self.contains(n_row, n_col) { Some((n_row, n_col)) }
+            else { None }
+        })
+    }
+
+    /// Apply `f` to every cell building a new grid with the same shape
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    /// Grid with rows turned into columns
+    pub fn transpose(&self) -> Grid<T>
+        where T: Clone
+    {
+        self.rebuild(self.height, self.width, |row, col| (col, row))
+    }
+
+    /// Grid rotated 90 degrees clockwise
+    pub fn rotate_clockwise(&self) -> Grid<T>
+        where T: Clone
+    {
+        self.rebuild(self.height, self.width, |row, col| (self.height - 1 - col, row))
+    }
+
+    /// Grid rotated 90 degrees counter clockwise
+    pub fn rotate_counter_clockwise(&self) -> Grid<T>
+        where T: Clone
+    {
+        self.rebuild(self.height, self.width, |row, col| (col, self.width - 1 - row))
+    }
+
+    /// Build grid of `width` x `height` where `source` maps each new position to a position in self
+    fn rebuild<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+        where T: Clone, F: Fn(usize, usize) -> (usize, usize)
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                let (src_row, src_col) = source(row, col);
+                cells.push(self.cells[src_row * self.width + src_col].clone());
+            }
+        }
+
+        Grid { cells, width, height }
+    }
+}
+
+/// Cells are right aligned to the widest cell and separated by a space
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self.cells.iter().map(|it| it.to_string()).collect();
+        let cell_width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+
+        for (row_n, row) in cells.chunks(self.width.max(1)).enumerate() {
+            if row_n > 0 { writeln!(f)? }
+
+            let line: Vec<String> = row.iter()
+                .map(|c| format!("{:>width$}", c, width = cell_width))
+                .collect();
+            write!(f, "{}", line.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridError};
+
+    #[test]
+    fn should_parse_whitespace_separated_grid() {
+        let grid: Grid<u32> = Grid::parse_whitespace("22 13  1\n 8  2 23\n").unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(&8), grid.get(1, 0));
+        assert_eq!(None, grid.get(2, 0));
+        assert_eq!(None, grid.get(0, 3));
+    }
+
+    #[test]
+    fn should_parse_char_and_separated_grids() {
+        let chars: Grid<u8> = Grid::parse_chars("123\n456").unwrap();
+        let separated: Grid<u8> = Grid::parse_separated("1,2,3\n4, 5,6", ',').unwrap();
+
+        assert_eq!(chars, separated);
+    }
+
+    #[test]
+    fn should_report_ragged_and_invalid_cells() {
+        let ragged = Grid::<u8>::parse_whitespace("1 2\n3").unwrap_err();
+        let invalid = Grid::<u8>::parse_chars("12\n3x").unwrap_err();
+
+        assert_eq!(GridError::Ragged { row: 1, expected: 2, found: 1 }, ragged);
+        assert!(matches!(invalid, GridError::Parse { row: 1, col: 1, .. }));
+    }
+
+    #[test]
+    fn should_iterate_rows_columns_and_diagonals() {
+        let grid = create_grid();
+
+        assert_eq!(Some(&[4, 5, 6][..]), grid.row(1));
+        assert_eq!(vec![2, 5, 8], grid.column(1).unwrap().copied().collect::<Vec<_>>());
+        assert_eq!(3, grid.columns().count());
+        assert_eq!(vec![1, 5, 9], grid.diagonal().copied().collect::<Vec<_>>());
+        assert_eq!(vec![3, 5, 7], grid.anti_diagonal().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_return_neighbours_inside_bounds() {
+        let grid = create_grid();
+
+        assert_eq!(vec![(0, 1), (1, 0)], grid.neighbours_4(0, 0).collect::<Vec<_>>());
+        assert_eq!(4, grid.neighbours_4(1, 1).count());
+        assert_eq!(3, grid.neighbours_8(2, 2).count());
+        assert_eq!(8, grid.neighbours_8(1, 1).count());
+        assert_eq!(0, grid.neighbours_8(3, 0).count());
+    }
+
+    #[test]
+    fn should_set_only_inside_bounds() {
+        let mut grid = create_grid();
+
+        assert_eq!(Some(5), grid.set(1, 1, 0));
+        assert_eq!(None, grid.set(3, 3, 0));
+        assert_eq!(Some(&0), grid.get(1, 1));
+    }
+
+    #[test]
+    fn should_transpose_and_rotate() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(Grid::from_rows(vec![vec![1, 4], vec![2, 5], vec![3, 6]]).unwrap(), grid.transpose());
+        assert_eq!(Grid::from_rows(vec![vec![4, 1], vec![5, 2], vec![6, 3]]).unwrap(), grid.rotate_clockwise());
+        assert_eq!(Grid::from_rows(vec![vec![3, 6], vec![2, 5], vec![1, 4]]).unwrap(), grid.rotate_counter_clockwise());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counter_clockwise());
+    }
+
+    #[test]
+    fn should_display_aligned_cells() {
+        let grid = Grid::from_rows(vec![vec![1, 22], vec![333, 4]]).unwrap();
+
+        assert_eq!("  1  22\n333   4", grid.to_string());
+    }
+
+    fn create_grid() -> Grid<u32> {
+        Grid::from_rows(vec![
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9]
+        ]).unwrap()
+    }
+}
@@ -0,0 +1,30 @@
+/// Quote and escape text as a JSON string
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::quote;
+
+    #[test]
+    fn should_escape_special_characters() {
+        assert_eq!("\"Dive!\"", quote("Dive!"));
+        assert_eq!("\"a\\\"b\\\\c\\nd\\u0001\"", quote("a\"b\\c\nd\u{1}"));
+    }
+}
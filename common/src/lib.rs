@@ -1,70 +1,350 @@
-use std::env;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
+use std::marker::PhantomData;
+use std::path::Path;
 use std::str::FromStr;
 
+mod answers;
+mod bench;
+mod blocks;
+mod cli;
+mod grid;
+mod json;
+mod output;
+mod solution;
+
+pub use answers::{AnswerCheck, AnswerInput, Answers, AnswersError, Verification, verify_and_exit, verify_day};
+pub use bench::{BenchReport, Stats, StepStats, bench_and_exit, bench_day, reports_to_json};
+pub use blocks::{
+    Block, Blocks, split_blocks, try_load_blocks, try_load_blocks_with, try_load_blocks_with_type,
+    try_load_header_and_blocks
+};
+pub use cli::{Cli, CliError, InputSource, Options};
+pub use grid::{Grid, GridError};
+pub use output::{Answer, Format, Value, render_reports};
+pub use solution::{Day, DayReport, Part, PartReport, Solution, run_day, run_day_from_file};
+
 const INPUT_DATA_FILE: &str = "resources/input_data.txt";
 const TEST_INPUT_DATA_FILE: &str = "resources/min_input_data.txt";
+const ANSWERS_FILE_NAME: &str = "answers.toml";
+const ANSWERS_FILE: &str = "resources/answers.toml";
+const STDIN_NAME: &str = "<stdin>";
+const STR_NAME: &str = "<string>";
 
-pub fn load_data(path: &str) -> Vec<String> {
-    let file = File::open(path)
-        .expect("Error loading file");
-    let reader = BufReader::new(file);
+/// Input path that reads from standard input
+pub const STDIN_PATH: &str = "-";
 
-    let mut data: Vec<String> = Vec::new();
-    for line in reader.lines() {
-        if let Ok(d) = line {
-            data.push(String::from(d.trim()));
-        };
+/// Reader over a file or standard input
+pub type InputReader = Box<dyn BufRead>;
+
+/// Error raised while loading puzzle input
+///
+/// `E` is the error of the `FromStr` implementation used to parse each line,
+/// loaders that do not parse lines use `Infallible`.
+#[derive(Debug)]
+pub enum InputError<E = Infallible> {
+    /// Input file could not be opened
+    Open { path: String, source: io::Error },
+    /// Line could not be read (e.g. invalid UTF-8)
+    Read { path: String, line: usize, source: io::Error },
+    /// Line could not be parsed into the requested type
+    Parse { path: String, line: usize, text: String, source: E }
+}
+
+impl InputError {
+    /// Convert an error raised before any parsing took place
+    pub fn into_parse_error<E>(self) -> InputError<E> {
+        match self {
+            InputError::Open { path, source } => InputError::Open { path, source },
+            InputError::Read { path, line, source } => InputError::Read { path, line, source },
+            InputError::Parse { source, .. } => match source {}
+        }
+    }
+}
+
+impl<E: Display> Display for InputError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Open { path, source } =>
+                write!(f, "unable to open {}: {}", path, source),
+            InputError::Read { path, line, source } =>
+                write!(f, "{}:{}: unable to read line: {}", path, line, source),
+            InputError::Parse { path, line, text, source } =>
+                write!(f, "{}:{}: unable to parse '{}': {}", path, line, text, source)
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for InputError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Open { source, .. } => Some(source),
+            InputError::Read { source, .. } => Some(source),
+            InputError::Parse { source, .. } => Some(source)
+        }
     }
+}
+
+/// Load trimmed lines of a file, panicking on any error
+pub fn load_data(path: &str) -> Vec<String> {
+    try_load_data(path).unwrap_or_else(|e| panic!("Error loading file: {}", e))
+}
+
+/// Load and parse each trimmed line of a file, panicking on any error
+pub fn load_data_with_type<T: FromStr>(path: &str) -> Vec<T>
+    where T::Err: Display
+{
+    try_load_data_with_type(path).unwrap_or_else(|e| panic!("Unable to parse data: {}", e))
+}
+
+/// Load trimmed lines of a file, `-` reads standard input
+pub fn try_load_data(path: &str) -> Result<Vec<String>, InputError> {
+    lines(path)?.collect()
+}
+
+/// Load and parse each trimmed line of a file, `-` reads standard input
+///
+/// Errors report the 1-based line number and text of the offending line.
+pub fn try_load_data_with_type<T: FromStr>(path: &str) -> Result<Vec<T>, InputError<T::Err>> {
+    parsed_lines(path)?.collect()
+}
+
+/// Load trimmed lines of a reader, `name` is only used to report errors
+pub fn load_data_from_reader<R: BufRead>(name: &str, reader: R) -> Result<Vec<String>, InputError> {
+    Lines::new(name, reader).collect()
+}
+
+/// Load and parse each trimmed line of a reader, `name` is only used to report errors
+pub fn load_data_with_type_from_reader<T: FromStr, R: BufRead>(
+    name: &str,
+    reader: R
+) -> Result<Vec<T>, InputError<T::Err>> {
+    ParsedLines::new(Lines::new(name, reader)).collect()
+}
+
+/// Split in-memory input into trimmed lines
+pub fn load_data_from_str(input: &str) -> Vec<String> {
+    input.lines().map(|line| line.trim().to_string()).collect()
+}
 
-    data
+/// Parse each trimmed line of in-memory input
+pub fn load_data_with_type_from_str<T: FromStr>(input: &str) -> Result<Vec<T>, InputError<T::Err>> {
+    load_data_with_type_from_reader(STR_NAME, input.as_bytes())
 }
 
-pub fn load_data_with_type<T: FromStr>(path: &str) -> Vec<T> {
+/// Lazily iterate over the trimmed lines of a file, `-` reads standard input
+///
+/// Only the current line is kept in memory, so arbitrarily large inputs can be processed.
+pub fn lines(path: &str) -> Result<Lines<InputReader>, InputError> {
+    if path == STDIN_PATH {
+        return Ok(Lines::new(STDIN_NAME, Box::new(io::stdin().lock())));
+    }
+
     let file = File::open(path)
-        .expect("Error loading file");
-    let reader = BufReader::new(file);
-
-    let mut data: Vec<T> = Vec::new();
-    for line in reader.lines() {
-        if let Ok(d) = line {
-            let parsed = match d.trim().parse() {
-                Ok(d) => d,
-                Err(_) => panic!("Unable to parse data")
-            };
-            data.push(parsed);
-        };
+        .map_err(|source| InputError::Open { path: path.to_string(), source })?;
+
+    Ok(Lines::new(path, Box::new(BufReader::new(file))))
+}
+
+/// Lazily iterate over the trimmed lines of a file parsed into `T`, `-` reads standard input
+pub fn parsed_lines<T: FromStr>(path: &str) -> Result<ParsedLines<T, InputReader>, InputError<T::Err>> {
+    lines(path)
+        .map(ParsedLines::new)
+        .map_err(InputError::into_parse_error)
+}
+
+/// Iterator over trimmed lines, see [`lines`]
+pub struct Lines<R> {
+    path: String,
+    lines: io::Lines<R>,
+    line_n: usize
+}
+
+impl<R: BufRead> Lines<R> {
+    /// Create iterator over `reader`, `path` is only used to report errors
+    pub fn new(path: &str, reader: R) -> Lines<R> {
+        Lines { path: path.to_string(), lines: reader.lines(), line_n: 0 }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<String, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line_n += 1;
+
+        Some(match line {
+            Ok(d) => Ok(String::from(d.trim())),
+            Err(source) => Err(InputError::Read { path: self.path.clone(), line: self.line_n, source })
+        })
     }
+}
+
+/// Iterator over trimmed lines parsed into `T`, see [`parsed_lines`]
+pub struct ParsedLines<T, R> {
+    lines: Lines<R>,
+    _type: PhantomData<T>
+}
 
-    data
+impl<T: FromStr, R: BufRead> ParsedLines<T, R> {
+    pub fn new(lines: Lines<R>) -> ParsedLines<T, R> {
+        ParsedLines { lines, _type: PhantomData }
+    }
 }
 
-pub fn get_input_file_path(config: &FileConfig) -> &str {
-    let env: Option<String> = env::args().nth(1);
+impl<T: FromStr, R: BufRead> Iterator for ParsedLines<T, R> {
+    type Item = Result<T, InputError<T::Err>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(e) => return Some(Err(e.into_parse_error()))
+        };
 
-    match env {
-        Some(s) => if s == "test" {&config.test_file} else {&config.prod_file},
-        _ => &config.prod_file
+        Some(line.parse().map_err(|source| InputError::Parse {
+            path: self.lines.path.clone(),
+            line: self.lines.line_n,
+            text: line,
+            source
+        }))
     }
 }
 
 pub struct FileConfig {
     pub test_file: String,
-    pub prod_file: String
+    pub prod_file: String,
+    /// Expected answers, see [`Answers`]
+    pub answers_file: String
 }
 
 impl FileConfig {
 
+    /// Create config with answers file placed next to `prod_file`
     pub fn new(prod_file: &str, test_file: &str) -> FileConfig {
-        FileConfig {test_file: test_file.to_string(), prod_file: prod_file.to_string()}
+        let answers_file = Path::new(prod_file).with_file_name(ANSWERS_FILE_NAME);
+        FileConfig {
+            test_file: test_file.to_string(),
+            prod_file: prod_file.to_string(),
+            answers_file: answers_file.to_string_lossy().to_string()
+        }
     }
 
     /// Create config assuming default structure
     pub fn default(base_path: &str) -> FileConfig {
         FileConfig {
             test_file: format!("{}/{}", base_path, TEST_INPUT_DATA_FILE),
-            prod_file: format!("{}/{}", base_path, INPUT_DATA_FILE)
+            prod_file: format!("{}/{}", base_path, INPUT_DATA_FILE),
+            answers_file: format!("{}/{}", base_path, ANSWERS_FILE)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::{
+        InputError, lines, load_data_from_reader, load_data_from_str, load_data_with_type_from_str, parsed_lines,
+        try_load_data, try_load_data_with_type
+    };
+
+    #[test]
+    fn should_load_trimmed_lines() {
+        let path = write_test_file("lines", " 1\n2 \n3\n");
+
+        let data = try_load_data(&path).unwrap();
+
+        assert_eq!(vec!["1", "2", "3"], data);
+    }
+
+    #[test]
+    fn should_report_missing_file() {
+        let err = try_load_data("./missing/input_data.txt").unwrap_err();
+
+        assert!(matches!(err, InputError::Open { ref path, .. } if path == "./missing/input_data.txt"));
+    }
+
+    #[test]
+    fn should_report_line_of_parse_error() {
+        let path = write_test_file("parse_error", "1\n2\nthree\n4\n");
+
+        let err = try_load_data_with_type::<i32>(&path).unwrap_err();
+
+        match err {
+            InputError::Parse { line, text, .. } => {
+                assert_eq!(3, line);
+                assert_eq!("three", text);
+            },
+            _ => panic!("Expected parse error")
         }
     }
+
+    #[test]
+    fn should_report_unreadable_line() {
+        let path = temp_path("unreadable");
+        fs::write(&path, b"1\n\xff\xfe\n3\n").unwrap();
+
+        let err = try_load_data(path.to_str().unwrap()).unwrap_err();
+
+        assert!(matches!(err, InputError::Read { line: 2, .. }));
+    }
+
+    #[test]
+    fn should_iterate_lines_lazily() {
+        let path = write_test_file("lazy_lines", "a \n b\n");
+
+        let mut it = lines(&path).unwrap();
+
+        assert_eq!("a", it.next().unwrap().unwrap());
+        assert_eq!("b", it.next().unwrap().unwrap());
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn should_yield_parsed_values_until_error() {
+        let path = write_test_file("lazy_parsed", "1\nx\n3\n");
+
+        let values: Vec<_> = parsed_lines::<u32>(&path).unwrap().collect();
+
+        assert_eq!(3, values.len());
+        assert_eq!(1, *values[0].as_ref().unwrap());
+        assert!(matches!(values[1], Err(InputError::Parse { line: 2, .. })));
+        assert_eq!(3, *values[2].as_ref().unwrap());
+    }
+
+    #[test]
+    fn should_load_data_from_str() {
+        assert_eq!(vec!["forward 5", "down 3"], load_data_from_str(" forward 5\ndown 3 \n"));
+        assert_eq!(vec![1, 2], load_data_with_type_from_str::<u8>("1\n2").unwrap());
+    }
+
+    #[test]
+    fn should_report_parse_error_from_str() {
+        let err = load_data_with_type_from_str::<u8>("1\n300").unwrap_err();
+
+        assert_eq!("<string>:2: unable to parse '300': number too large to fit in target type", err.to_string());
+    }
+
+    #[test]
+    fn should_load_data_from_reader() {
+        let reader = std::io::Cursor::new("a\nb\n");
+
+        assert_eq!(vec!["a", "b"], load_data_from_reader("cursor", reader).unwrap());
+    }
+
+    fn write_test_file(name: &str, content: &str) -> String {
+        let path = temp_path(name);
+        fs::write(&path, content).unwrap();
+
+        path.to_str().unwrap().to_string()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc_common_{}_{}.txt", name, std::process::id()))
+    }
 }
@@ -0,0 +1,273 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::json;
+use crate::{DayReport, PartReport};
+
+/// Answer or detail value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Text(String)
+}
+
+impl Value {
+    fn to_json(&self) -> String {
+        match self {
+            Value::Int(num) => num.to_string(),
+            Value::Text(text) => json::quote(text)
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(num) => write!(f, "{}", num),
+            Value::Text(text) => write!(f, "{}", text)
+        }
+    }
+}
+
+macro_rules! impl_int_value {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(num: $t) -> Self {
+                Value::Int(num as i64)
+            }
+        })*
+    };
+}
+
+impl_int_value!(i32, i64, u32, usize);
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+/// Named part answer with optional named details, e.g. the values the answer was computed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    /// Snake case name, e.g. `power_consumption`
+    pub name: &'static str,
+    pub value: Value,
+    pub details: Vec<(&'static str, Value)>
+}
+
+impl Answer {
+    pub fn new<V: Into<Value>>(name: &'static str, value: V) -> Answer {
+        Answer { name, value: value.into(), details: Vec::new() }
+    }
+
+    /// Add named detail value
+    pub fn with<V: Into<Value>>(mut self, name: &'static str, value: V) -> Answer {
+        self.details.push((name, value.into()));
+        self
+    }
+}
+
+/// Only the answer value is displayed
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// Output format of answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            unk => Err(format!("unknown format '{}'", unk))
+        }
+    }
+}
+
+const TSV_HEADER: &str = "day\tpart\tname\tvalue";
+
+impl DayReport {
+    pub fn render(&self, format: Format) -> String {
+        render_reports(std::slice::from_ref(self), format)
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines = vec![format!("Day {}: {}", self.day, self.title)];
+        if let Some(parse_time) = self.parse_time {
+            lines.push(format!("  Parse: {:?}", parse_time));
+        }
+        for part in &self.parts {
+            let time = part.time.map_or(String::new(), |t| format!(" ({:?})", t));
+            lines.push(format!(
+                "  Part {} - {}: {}{}",
+                part.part,
+                humanize(part.answer.name),
+                part.answer.value,
+                time
+            ));
+            for (name, value) in &part.answer.details {
+                lines.push(format!("    {}: {}", humanize(name), value));
+            }
+        }
+        if self.parse_time.is_some() {
+            lines.push(format!("  Total: {:?}", self.total_time()));
+        }
+
+        lines.join("\n")
+    }
+
+    fn to_json(&self) -> String {
+        let parts: Vec<String> = self.parts.iter().map(part_to_json).collect();
+        let parse_time = self.parse_time.map_or(String::new(), |t| format!(",\"parse_ns\":{}", t.as_nanos()));
+
+        format!(
+            "{{\"day\":{},\"title\":{}{},\"parts\":[{}]}}",
+            self.day,
+            json::quote(self.title),
+            parse_time,
+            parts.join(",")
+        )
+    }
+
+    fn to_tsv_rows(&self) -> Vec<String> {
+        let mut rows = Vec::new();
+        for part in &self.parts {
+            let answer = &part.answer;
+            let values = std::iter::once((answer.name, &answer.value))
+                .chain(answer.details.iter().map(|(name, value)| (*name, value)));
+            for (name, value) in values {
+                rows.push(format!("{}\t{}\t{}\t{}", self.day, part.part, name, tsv_escape(&value.to_string())));
+            }
+        }
+
+        rows
+    }
+}
+
+fn part_to_json(part: &PartReport) -> String {
+    let details: Vec<String> = part.answer.details.iter()
+        .map(|(name, value)| format!("{}:{}", json::quote(name), value.to_json()))
+        .collect();
+    let time = part.time.map_or(String::new(), |t| format!(",\"time_ns\":{}", t.as_nanos()));
+
+    format!(
+        "{{\"part\":{},\"name\":{},\"value\":{},\"details\":{{{}}}{}}}",
+        part.part,
+        json::quote(part.answer.name),
+        part.answer.value.to_json(),
+        details.join(","),
+        time
+    )
+}
+
+/// Render reports of one or several days
+///
+/// JSON output is an array of days and TSV output has a single header row.
+pub fn render_reports(reports: &[DayReport], format: Format) -> String {
+    match format {
+        Format::Text => {
+            let reports: Vec<String> = reports.iter().map(DayReport::to_text).collect();
+            reports.join("\n")
+        },
+        Format::Json => {
+            let reports: Vec<String> = reports.iter().map(DayReport::to_json).collect();
+            format!("[{}]", reports.join(","))
+        },
+        Format::Tsv => {
+            let rows: Vec<String> = std::iter::once(TSV_HEADER.to_string())
+                .chain(reports.iter().flat_map(DayReport::to_tsv_rows))
+                .collect();
+            rows.join("\n")
+        }
+    }
+}
+
+/// `power_consumption` -> `power consumption`
+fn humanize(name: &str) -> String {
+    name.replace('_', " ")
+}
+
+fn tsv_escape(text: &str) -> String {
+    text.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{DayReport, Part, PartReport};
+    use super::{Answer, Format};
+
+    #[test]
+    fn should_render_text() {
+        assert_eq!(
+            "Day 2: Dive!\n  Part 1 - depth x position: 150\n    position: 15\n    depth: 10",
+            create_report().render(Format::Text)
+        );
+    }
+
+    #[test]
+    fn should_render_json() {
+        assert_eq!(
+            "[{\"day\":2,\"title\":\"Dive!\",\"parts\":[{\"part\":1,\"name\":\"depth_x_position\",\"value\":150,\
+            \"details\":{\"position\":15,\"depth\":10}}]}]",
+            create_report().render(Format::Json)
+        );
+    }
+
+    #[test]
+    fn should_render_tsv() {
+        assert_eq!(
+            "day\tpart\tname\tvalue\n2\t1\tdepth_x_position\t150\n2\t1\tposition\t15\n2\t1\tdepth\t10",
+            create_report().render(Format::Tsv)
+        );
+    }
+
+    #[test]
+    fn should_render_timings_when_present() {
+        let mut report = create_report();
+        report.parse_time = Some(Duration::from_micros(3));
+        report.parts[0].time = Some(Duration::from_micros(2));
+
+        assert!(report.render(Format::Text).contains("Part 1 - depth x position: 150 (2µs)"));
+        assert!(report.render(Format::Json).contains("\"parse_ns\":3000"));
+        assert!(report.render(Format::Json).contains("\"time_ns\":2000"));
+    }
+
+    #[test]
+    fn should_parse_format() {
+        assert_eq!(Ok(Format::Tsv), "tsv".parse());
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    fn create_report() -> DayReport {
+        DayReport {
+            day: 2,
+            title: "Dive!",
+            parse_time: None,
+            parts: vec![PartReport {
+                part: Part::One,
+                answer: Answer::new("depth_x_position", 150).with("position", 15).with("depth", 10),
+                time: None
+            }]
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::Answer;
+
+/// Puzzle solution of a day
+pub trait Solution {
+    /// Parse puzzle input into the solution state
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> where Self: Sized;
+
+    /// Answer part one, failing when the parsed input does not allow it
+    fn part_one(&self) -> Result<Answer, Box<dyn Error>>;
+
+    /// Answer part two, failing when the parsed input does not allow it
+    fn part_two(&self) -> Result<Answer, Box<dyn Error>>;
+}
+
+/// Puzzle part selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+type ParseFn = fn(&str) -> Result<Box<dyn Solution>, Box<dyn Error>>;
+
+/// Registration of a day solution
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    /// Day crate directory, used to find default input files
+    pub base_path: &'static str,
+    parse: ParseFn
+}
+
+impl Day {
+    pub fn new<S: Solution + 'static>(number: u32, title: &'static str, base_path: &'static str) -> Day {
+        Day { number, title, base_path, parse: parse_boxed::<S> }
+    }
+
+    /// Parse input into the registered solution
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>, Box<dyn Error>> {
+        (self.parse)(input)
+    }
+}
+
+fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, Box<dyn Error>> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+/// Answer of a puzzle part and the time it took to compute, when timed
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Option<Duration>
+}
+
+/// Answers and timings of a day run, see `DayReport::render` for output
+pub struct DayReport {
+    pub day: u32,
+    pub title: &'static str,
+    pub parse_time: Option<Duration>,
+    pub parts: Vec<PartReport>
+}
+
+impl DayReport {
+    /// Report without answers nor timings
+    pub fn new(day: &Day) -> DayReport {
+        DayReport { day: day.number, title: day.title, parse_time: None, parts: Vec::new() }
+    }
+
+    /// Add untimed part answer
+    pub fn add(&mut self, part: Part, answer: Answer) {
+        self.parts.push(PartReport { part, answer, time: None });
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time.unwrap_or_default() + self.parts.iter().filter_map(|p| p.time).sum::<Duration>()
+    }
+}
+
+/// Run `parts` of a day over `input` timing each step
+pub fn run_day(day: &Day, input: &str, parts: &[Part]) -> Result<DayReport, Box<dyn Error>> {
+    let start = Instant::now();
+    let solution = day.parse(input)?;
+    let parse_time = start.elapsed();
+
+    let parts = parts.iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => solution.part_one(),
+                Part::Two => solution.part_two()
+            }?;
+
+            Ok(PartReport { part: *part, answer, time: Some(start.elapsed()) })
+        })
+        .collect::<Result<_, Box<dyn Error>>>()?;
+
+    Ok(DayReport { day: day.number, title: day.title, parse_time: Some(parse_time), parts })
+}
+
+/// Read input file and run `parts` of a day over it
+pub fn run_day_from_file(day: &Day, path: &str, parts: &[Part]) -> Result<DayReport, Box<dyn Error>> {
+    let input = fs::read_to_string(path)
+        .map_err(|e| format!("unable to open {}: {}", path, e))?;
+
+    run_day(day, &input, parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::Answer;
+    use super::{Day, Part, Solution, run_day};
+
+    struct Sum {
+        values: Vec<u32>
+    }
+
+    impl Solution for Sum {
+        fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+            let values = input.split_whitespace()
+                .map(|it| it.parse())
+                .collect::<Result<_, _>>()?;
+
+            Ok(Sum { values })
+        }
+
+        fn part_one(&self) -> Result<Answer, Box<dyn Error>> {
+            Ok(Answer::new("sum", self.values.iter().sum::<u32>()))
+        }
+
+        fn part_two(&self) -> Result<Answer, Box<dyn Error>> {
+            self.values.iter()
+                .try_fold(1u32, |product, value| product.checked_mul(*value))
+                .map(|product| Answer::new("product", product))
+                .ok_or_else(|| "product overflows".into())
+        }
+    }
+
+    #[test]
+    fn should_run_selected_parts() {
+        let day = Day::new::<Sum>(0, "Sum", ".");
+
+        let report = run_day(&day, "2 3 4", &[Part::Two]).unwrap();
+
+        assert_eq!(1, report.parts.len());
+        assert_eq!(Part::Two, report.parts[0].part);
+        assert_eq!("24", report.parts[0].answer.to_string());
+    }
+
+    #[test]
+    fn should_fail_on_invalid_input() {
+        let day = Day::new::<Sum>(0, "Sum", ".");
+
+        assert!(run_day(&day, "2 x", &Part::ALL).is_err());
+    }
+
+    #[test]
+    fn should_fail_on_part_error() {
+        let day = Day::new::<Sum>(0, "Sum", ".");
+
+        assert!(run_day(&day, "65536 65536", &[Part::One]).is_ok());
+        assert_eq!("product overflows", run_day(&day, "65536 65536", &Part::ALL).err().unwrap().to_string());
+    }
+}
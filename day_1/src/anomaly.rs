@@ -0,0 +1,130 @@
+use crate::sonar::{AnalysisError, Depth, require_data, require_window};
+use crate::window::rolling_windows;
+
+/// Kind of suspicious reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// Reading rises above the previous one by more than the spike threshold
+    Spike,
+    /// Reading falls below the previous one by more than the dropout threshold
+    Dropout,
+    /// Reading z-score against the preceding window exceeds the band
+    OutOfBand
+}
+
+/// Suspicious reading of a sonar series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anomaly {
+    /// Reading position starting by 0
+    pub index: usize,
+    pub depth: Depth,
+    pub kind: AnomalyKind
+}
+
+/// Anomaly detector thresholds, `None` disables the detector
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnomalyConfig {
+    pub spike_threshold: Option<i64>,
+    pub dropout_threshold: Option<i64>,
+    /// Number of preceding readings the z-score is computed against
+    pub window_size: usize,
+    pub z_score: Option<f64>
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        AnomalyConfig { spike_threshold: Some(100), dropout_threshold: Some(100), window_size: 10, z_score: Some(3.0) }
+    }
+}
+
+/// Flag suspicious readings of a sonar series, ordered by index
+///
+/// A reading can be reported once per kind. Readings following a window without any variance are never
+/// out of band, as their z-score is undefined.
+///
+/// Fails with less than two readings, or when z-score detection is enabled with less than `window_size + 1`
+/// readings or a `window_size` of 0.
+pub fn detect_anomalies(sonar_data: &[Depth], config: &AnomalyConfig) -> Result<Vec<Anomaly>, AnalysisError> {
+    require_data(2, sonar_data.len())?;
+    if config.z_score.is_some() {
+        require_window(config.window_size)?;
+        require_data(config.window_size + 1, sonar_data.len())?;
+    }
+
+    let mut anomalies = Vec::new();
+    for (idx, pair) in sonar_data.windows(2).enumerate() {
+        let (index, depth) = (idx + 1, pair[1]);
+        let delta = pair[1] as i64 - pair[0] as i64;
+        if config.spike_threshold.is_some_and(|threshold| delta > threshold) {
+            anomalies.push(Anomaly { index, depth, kind: AnomalyKind::Spike });
+        }
+        if config.dropout_threshold.is_some_and(|threshold| -delta > threshold) {
+            anomalies.push(Anomaly { index, depth, kind: AnomalyKind::Dropout });
+        }
+    }
+
+    if let Some(z_score) = config.z_score {
+        // Each window is compared with the reading right after it
+        let readings = sonar_data.iter().enumerate().skip(config.window_size);
+        for (window, (index, depth)) in rolling_windows(sonar_data.iter().copied(), config.window_size).zip(readings) {
+            let std_dev = window.std_dev();
+            if std_dev > 0.0 && ((*depth as f64 - window.mean()) / std_dev).abs() > z_score {
+                anomalies.push(Anomaly { index, depth: *depth, kind: AnomalyKind::OutOfBand });
+            }
+        }
+    }
+
+    // Stable sort keeps spike/dropout before out of band for the same reading
+    anomalies.sort_by_key(|anomaly| anomaly.index);
+
+    Ok(anomalies)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sonar::AnalysisError;
+    use super::{Anomaly, AnomalyConfig, AnomalyKind, detect_anomalies};
+
+    #[test]
+    fn should_detect_spikes_and_dropouts() {
+        let config = AnomalyConfig { spike_threshold: Some(50), dropout_threshold: Some(50), window_size: 3, z_score: None };
+
+        let anomalies = detect_anomalies(&[100, 102, 200, 104, 103, 0, 105], &config).unwrap();
+
+        let kinds: Vec<(usize, AnomalyKind)> = anomalies.iter().map(|a| (a.index, a.kind)).collect();
+        assert_eq!(vec![
+            (2, AnomalyKind::Spike),
+            (3, AnomalyKind::Dropout),
+            (5, AnomalyKind::Dropout),
+            (6, AnomalyKind::Spike)
+        ], kinds);
+    }
+
+    #[test]
+    fn should_detect_readings_out_of_band() {
+        let config = AnomalyConfig { spike_threshold: None, dropout_threshold: None, window_size: 4, z_score: Some(3.0) };
+
+        let anomalies = detect_anomalies(&[100, 101, 99, 100, 101, 130, 100], &config);
+
+        assert_eq!(Ok(vec![Anomaly { index: 5, depth: 130, kind: AnomalyKind::OutOfBand }]), anomalies);
+    }
+
+    #[test]
+    fn should_skip_windows_without_variance() {
+        let config = AnomalyConfig { spike_threshold: None, dropout_threshold: None, window_size: 3, z_score: Some(1.0) };
+
+        assert_eq!(Ok(Vec::new()), detect_anomalies(&[100, 100, 100, 500], &config));
+    }
+
+    #[test]
+    fn should_fail_on_short_series() {
+        let config = AnomalyConfig { spike_threshold: Some(10), dropout_threshold: None, window_size: 3, z_score: None };
+        let z_config = AnomalyConfig { z_score: Some(2.0), ..config };
+
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 0 }), detect_anomalies(&[], &config));
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 1 }), detect_anomalies(&[100], &config));
+        assert_eq!(Ok(Vec::new()), detect_anomalies(&[100, 100, 100], &config));
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 4, found: 3 }), detect_anomalies(&[100, 100, 100], &z_config));
+        assert_eq!(Err(AnalysisError::EmptyWindow), detect_anomalies(&[100, 100], &AnomalyConfig { window_size: 0, ..z_config }));
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+
+use common::Format;
+
+use crate::sonar::{AnalysisError, Depth, require_data, require_window};
+use crate::window::rolling_windows;
+
+/// Kind of change between two consecutive values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Increase,
+    Decrease,
+    Equal
+}
+
+/// Policy classifying the change from a previous value to the current one
+///
+/// Implemented by `ChangePolicy` and by any `Fn(i64, i64) -> Change` closure.
+pub trait Comparison {
+    fn classify(&self, previous: i64, current: i64) -> Change;
+}
+
+impl<F: Fn(i64, i64) -> Change> Comparison for F {
+    fn classify(&self, previous: i64, current: i64) -> Change {
+        self(previous, current)
+    }
+}
+
+/// Built-in comparison policies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangePolicy {
+    /// Any difference is a change
+    Strict,
+    /// Differences up to the given absolute amount are considered equal
+    Threshold(i64),
+    /// Differences up to the given percentage of the previous value are considered equal
+    Percentage(f64)
+}
+
+impl Comparison for ChangePolicy {
+    fn classify(&self, previous: i64, current: i64) -> Change {
+        let delta = current - previous;
+        let is_plateau = match self {
+            ChangePolicy::Strict => delta == 0,
+            ChangePolicy::Threshold(threshold) => delta.abs() <= *threshold,
+            ChangePolicy::Percentage(percentage) => delta.abs() as f64 * 100.0 <= percentage * previous.abs() as f64
+        };
+
+        if is_plateau { Change::Equal }
+        else if delta > 0 { Change::Increase }
+        else { Change::Decrease }
+    }
+}
+
+/// Count of each kind of change over a series of values
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeBreakdown {
+    pub increases: usize,
+    pub decreases: usize,
+    pub equal: usize
+}
+
+impl ChangeBreakdown {
+    /// Number of compared pairs
+    pub fn total(&self) -> usize {
+        self.increases + self.decreases + self.equal
+    }
+
+    fn add(&mut self, change: Change) {
+        match change {
+            Change::Increase => self.increases += 1,
+            Change::Decrease => self.decreases += 1,
+            Change::Equal => self.equal += 1
+        }
+    }
+}
+
+/// Classify every pair of consecutive values, fails with less than two values
+pub fn change_breakdown<I, C>(values: I, comparison: &C) -> Result<ChangeBreakdown, AnalysisError>
+    where I: IntoIterator<Item = i64>, C: Comparison + ?Sized
+{
+    let mut breakdown = ChangeBreakdown::default();
+    let mut values = values.into_iter();
+    let mut previous = match values.next() {
+        Some(value) => value,
+        None => return Err(AnalysisError::NotEnoughData { required: 2, found: 0 })
+    };
+
+    for value in values {
+        breakdown.add(comparison.classify(previous, value));
+        previous = value;
+    }
+
+    // A single value has nothing to compare with
+    if breakdown.total() == 0 { return Err(AnalysisError::NotEnoughData { required: 2, found: 1 }) }
+
+    Ok(breakdown)
+}
+
+/// Breakdown of changes between consecutive measurements, fails with less than two measurements
+pub fn single_measurement_breakdown<I, C>(sonar_data: I, comparison: &C) -> Result<ChangeBreakdown, AnalysisError>
+    where I: IntoIterator<Item = Depth>, C: Comparison + ?Sized
+{
+    change_breakdown(sonar_data.into_iter().map(i64::from), comparison)
+}
+
+/// Breakdown of changes between sums of `window_size` consecutive measurements
+///
+/// Fails when `window_size` is 0 or there are not two windows to compare, i.e. less than `window_size + 1`
+/// measurements.
+pub fn windowed_measurement_breakdown<I, C>(
+    sonar_data: I,
+    window_size: usize,
+    comparison: &C
+) -> Result<ChangeBreakdown, AnalysisError>
+    where I: IntoIterator<Item = Depth>, C: Comparison + ?Sized
+{
+    require_window(window_size)?;
+
+    let mut count = 0;
+    let sonar_data = sonar_data.into_iter().inspect(|_| count += 1);
+    let breakdown = change_breakdown(rolling_windows(sonar_data, window_size).map(|window| window.sum), comparison);
+
+    breakdown.map_err(|_| AnalysisError::NotEnoughData { required: window_size + 1, found: count })
+}
+
+/// Change breakdown of the sums of windows of a given size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowBreakdown {
+    pub window_size: usize,
+    /// Fails when there are less than `window_size + 1` measurements
+    pub breakdown: Result<ChangeBreakdown, AnalysisError>
+}
+
+/// Strict change breakdowns for several window sizes in a single traversal
+///
+/// Consecutive window sums of size `w` only differ by the entering and leaving measurements, so comparing
+/// the sums is comparing `depth[i]` with `depth[i - w]`. Only the last `max(window_sizes)` measurements are
+/// kept in memory. Fails when any window size is 0.
+pub fn multi_window_breakdowns<I>(sonar_data: I, window_sizes: &[usize]) -> Result<Vec<WindowBreakdown>, AnalysisError>
+    where I: IntoIterator<Item = Depth>
+{
+    window_sizes.iter().try_for_each(|size| require_window(*size))?;
+
+    let mut count = 0;
+    let max_size = window_sizes.iter().copied().max().unwrap_or(0);
+    let mut breakdowns = vec![ChangeBreakdown::default(); window_sizes.len()];
+    let mut recent: VecDeque<Depth> = VecDeque::with_capacity(max_size + 1);
+    for depth in sonar_data {
+        count += 1;
+        for (size, breakdown) in window_sizes.iter().zip(breakdowns.iter_mut()) {
+            if let Some(leaving) = recent.len().checked_sub(*size).map(|idx| recent[idx]) {
+                breakdown.add(ChangePolicy::Strict.classify(leaving as i64, depth as i64));
+            }
+        }
+
+        recent.push_back(depth);
+        if recent.len() > max_size { recent.pop_front(); }
+    }
+
+    let rows = window_sizes.iter().zip(breakdowns)
+        .map(|(window_size, breakdown)| WindowBreakdown {
+            window_size: *window_size,
+            breakdown: require_data(window_size + 1, count).map(|_| breakdown)
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/// Render window breakdowns as a table, one row per window size
+///
+/// Window sizes without enough data have empty cells in text and TSV, and `null` counts in JSON.
+pub fn render_window_table(rows: &[WindowBreakdown], format: Format) -> String {
+    let cells = |row: &WindowBreakdown, missing: &str| match row.breakdown {
+        Ok(breakdown) => [breakdown.increases, breakdown.decreases, breakdown.equal].map(|count| count.to_string()),
+        Err(_) => [(); 3].map(|_| missing.to_string())
+    };
+
+    match format {
+        Format::Text => {
+            let mut lines = vec![format!("{:>8} {:>10} {:>10} {:>10}", "window", "increases", "decreases", "equal")];
+            lines.extend(rows.iter().map(|row| {
+                let [increases, decreases, equal] = cells(row, "-");
+                format!("{:>8} {:>10} {:>10} {:>10}", row.window_size, increases, decreases, equal)
+            }));
+            lines.join("\n")
+        },
+        Format::Json => {
+            let rows: Vec<String> = rows.iter()
+                .map(|row| {
+                    let [increases, decreases, equal] = cells(row, "null");
+                    format!(
+                        "{{\"window\":{},\"increases\":{},\"decreases\":{},\"equal\":{}}}",
+                        row.window_size, increases, decreases, equal
+                    )
+                })
+                .collect();
+            format!("[{}]", rows.join(","))
+        },
+        Format::Tsv => {
+            let mut lines = vec!["window\tincreases\tdecreases\tequal".to_string()];
+            lines.extend(rows.iter().map(|row| {
+                let [increases, decreases, equal] = cells(row, "");
+                format!("{}\t{}\t{}\t{}", row.window_size, increases, decreases, equal)
+            }));
+            lines.join("\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Format;
+
+    use crate::fixtures::EXAMPLE;
+    use crate::sonar::AnalysisError;
+    use super::{
+        Change, ChangeBreakdown, ChangePolicy, Comparison, WindowBreakdown, multi_window_breakdowns,
+        render_window_table, single_measurement_breakdown, windowed_measurement_breakdown
+    };
+
+    #[test]
+    fn should_break_down_strict_changes() {
+        let breakdown = single_measurement_breakdown([1, 2, 2, 1], &ChangePolicy::Strict).unwrap();
+
+        assert_eq!(ChangeBreakdown { increases: 1, decreases: 1, equal: 1 }, breakdown);
+        assert_eq!(3, breakdown.total());
+        assert_eq!(7, single_measurement_breakdown(EXAMPLE, &ChangePolicy::Strict).unwrap().increases);
+    }
+
+    #[test]
+    fn should_break_down_windowed_changes() {
+        let breakdown = windowed_measurement_breakdown(EXAMPLE, 3, &ChangePolicy::Strict);
+
+        assert_eq!(Ok(ChangeBreakdown { increases: 5, decreases: 1, equal: 1 }), breakdown);
+    }
+
+    #[test]
+    fn should_ignore_changes_within_threshold() {
+        let breakdown = single_measurement_breakdown(EXAMPLE, &ChangePolicy::Threshold(5));
+
+        assert_eq!(Ok(ChangeBreakdown { increases: 4, decreases: 2, equal: 3 }), breakdown);
+    }
+
+    #[test]
+    fn should_ignore_changes_within_percentage() {
+        assert_eq!(Change::Equal, ChangePolicy::Percentage(10.0).classify(100, 110));
+        assert_eq!(Change::Increase, ChangePolicy::Percentage(10.0).classify(100, 111));
+        assert_eq!(Change::Decrease, ChangePolicy::Percentage(10.0).classify(-100, -111));
+        assert_eq!(Change::Increase, ChangePolicy::Percentage(10.0).classify(0, 1));
+    }
+
+    #[test]
+    fn should_accept_custom_comparison() {
+        let doubles = |previous: i64, current: i64| if current >= previous * 2 { Change::Increase } else { Change::Equal };
+
+        let breakdown = single_measurement_breakdown([1, 2, 3, 6], &doubles);
+
+        assert_eq!(Ok(ChangeBreakdown { increases: 2, decreases: 0, equal: 1 }), breakdown);
+    }
+
+    #[test]
+    fn should_distinguish_short_inputs_from_no_changes() {
+        let strict = &ChangePolicy::Strict;
+
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 0 }), single_measurement_breakdown([], strict));
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 1 }), single_measurement_breakdown([5], strict));
+        assert_eq!(Ok(ChangeBreakdown { increases: 0, decreases: 0, equal: 1 }), single_measurement_breakdown([5, 5], strict));
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 4, found: 3 }),
+            windowed_measurement_breakdown([1, 2, 3], 3, strict)
+        );
+        assert_eq!(Err(AnalysisError::EmptyWindow), windowed_measurement_breakdown([1, 2, 3], 0, strict));
+        assert_eq!(Err(AnalysisError::EmptyWindow), multi_window_breakdowns([1, 2, 3], &[1, 0]));
+    }
+
+    #[test]
+    fn should_match_single_window_breakdowns() {
+        let data: Vec<i32> = (0..300).map(|i| (i * 7919 % 113) - (i % 5) * 3).collect();
+        let sizes = [1, 3, 5, 10, 299, 300, 400];
+
+        let breakdowns = multi_window_breakdowns(data.iter().copied(), &sizes).unwrap();
+
+        for (size, breakdown) in sizes.iter().zip(breakdowns) {
+            assert_eq!(*size, breakdown.window_size);
+            assert_eq!(windowed_measurement_breakdown(data.iter().copied(), *size, &ChangePolicy::Strict), breakdown.breakdown);
+        }
+    }
+
+    #[test]
+    fn should_render_window_table() {
+        let rows = multi_window_breakdowns(EXAMPLE, &[1, 3, 10]).unwrap();
+
+        assert_eq!(
+            WindowBreakdown { window_size: 1, breakdown: Ok(ChangeBreakdown { increases: 7, decreases: 2, equal: 0 }) },
+            rows[0]
+        );
+        assert_eq!(
+            "window\tincreases\tdecreases\tequal\n1\t7\t2\t0\n3\t5\t1\t1\n10\t\t\t",
+            render_window_table(&rows, Format::Tsv)
+        );
+        assert!(render_window_table(&rows, Format::Json).ends_with("{\"window\":10,\"increases\":null,\"decreases\":null,\"equal\":null}]"));
+    }
+}
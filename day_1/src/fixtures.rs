@@ -0,0 +1,2 @@
+/// Sonar sweep of the puzzle example
+pub const EXAMPLE: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
@@ -0,0 +1,13 @@
+pub mod anomaly;
+pub mod compare;
+pub mod profile;
+pub mod sonar;
+pub mod stats;
+pub mod trend;
+pub mod window;
+mod solution;
+#[cfg(test)]
+mod fixtures;
+
+pub use solution::{SonarSweep, both_answers, day, part_one_answer, part_two_answer};
+pub use sonar::{single_measurement_depth_increase_count, windowed_measurement_depth_increase_count};
@@ -1,72 +1,185 @@
-use std::fs::File;
-use std::io::{BufReader, prelude::*};
-use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::process;
+use std::str::FromStr;
 
-const INPUT_DATA_FILE: &str = "./resources/input_data.txt";
-const TEST_INPUT_DATA_FILE: &str = "./resources/min_input_data.txt";
+use common::{Cli, DayReport, FileConfig, Options, Part};
+use day_1::compare::{multi_window_breakdowns, render_window_table};
+use day_1::profile::{profile_series, render_svg, render_terminal};
+use day_1::sonar::{AnalysisError, Depth, ReadingPolicy, read_sonar_data, sonar_readings};
+use day_1::stats::SonarStats;
+use day_1::{both_answers, part_one_answer, part_two_answer};
+
+const DEFAULT_HISTOGRAM_WIDTH: i64 = 10;
+const DEFAULT_PLOT_WIDTH: usize = 80;
+const PLOT_HEIGHT: usize = 20;
+const SVG_SIZE: (u32, u32) = (800, 300);
+/// Options replacing answers by another output, `--plot` and `--svg` both render the depth profile
+const MODES: [&[&str]; 3] = [&["--stats"], &["--windows"], &["--plot", "--svg"]];
+/// Options tuning a mode, with the mode options any of which they require
+const MODE_OPTIONS: [(&str, &[&str]); 3] = [
+    ("--histogram-width", &["--stats"]),
+    ("--plot-window", &["--plot", "--svg"]),
+    ("--plot-width", &["--plot", "--svg"])
+];
 
 fn main() {
-    let env_arg: Option<String> = env::args().nth(1);
-    let sonar_data = load_data(get_input_file_path(env_arg));
+    let options = Cli::new("day_1")
+        .flag("--stats", "Print statistics of the sonar series instead of answers")
+        .option("--histogram-width", "n", "Step histogram bucket width, requires --stats, defaults to 10")
+        .flag("--plot", "Plot the depth profile in the terminal instead of answers")
+        .option("--svg", "path", "Write the depth profile as SVG to <path> instead of answers")
+        .option("--plot-window", "n", "Plot the mean of <n> measurements windows, requires --plot or --svg, defaults to 1")
+        .option("--plot-width", "n", "Downsample the plot to <n> columns, requires --plot or --svg, defaults to 80")
+        .option("--windows", "list", "Print increase counts for comma separated window sizes instead of answers")
+        .option("--on-invalid", "strict|skip|interpolate", "Malformed readings handling, defaults to strict")
+        .parse_env();
+    check_modes(&options);
+    let config = FileConfig::default("./day_1");
+    let day = day_1::day();
+    options.run_shared_modes(&day, &config);
+    let sonar_data = load_data(&options, &config);
 
-    let depth_increase_count = windowed_measurement_depth_increase_count(&sonar_data, 3);
-    println!("Number of depth increments: {}", depth_increase_count);
-}
+    if options.flag("--stats") {
+        print_stats(&options, &config, sonar_data.collect());
+        return;
+    }
+
+    if let Some(windows) = options.value("--windows") {
+        print_window_table(&options, &config, windows, sonar_data);
+        return;
+    }
 
-fn windowed_measurement_depth_increase_count(sonar_data: &Vec<i32>, window_size: usize) -> i32 {
-    if sonar_data.len() < window_size { return 0 }
+    if options.flag("--plot") || options.flag("--svg") {
+        plot_profile(&options, &config, sonar_data.collect());
+        return;
+    }
 
-    let mut depth_increase_count = 0;
-    let mut previous_measure: i32 = sonar_data[..window_size].iter().sum();
-    let last_valid_idx = sonar_data.len() - window_size + 1;
-    for idx in 1..last_valid_idx {
-        let measure: i32 = sonar_data[idx..idx+window_size].iter().sum();
-        if measure > previous_measure {
-            depth_increase_count += 1;
-        }
+    let answers = match options.part {
+        Some(Part::One) => part_one_answer(sonar_data).map(|answer| vec![(Part::One, answer)]),
+        Some(Part::Two) => part_two_answer(sonar_data).map(|answer| vec![(Part::Two, answer)]),
+        None => both_answers(sonar_data).map(|(one, two)| vec![(Part::One, one), (Part::Two, two)])
+    };
 
-        previous_measure = measure;
+    let mut report = DayReport::new(&day);
+    for (part, answer) in answers.unwrap_or_else(|e| analysis_failed(&options, &config, e)) {
+        report.add(part, answer);
     }
 
-    depth_increase_count
+    options.print_report(&report);
 }
 
-fn single_measurement_depth_increase_count(sonar_data: &Vec<i32>) -> i32 {
+/// Exit with usage error status when options of different modes are combined or used without their mode
+fn check_modes(options: &Options) {
+    let selected: Vec<&str> = MODES.iter()
+        .filter_map(|mode| mode.iter().copied().find(|option| options.flag(option)))
+        .collect();
 
-    let mut depth_increase_count = 0;
-    let mut previous_measure = sonar_data[0];
-    for measure in &sonar_data[1..] {
-        if *measure > previous_measure {
-            depth_increase_count += 1;
+    if let [first, second, ..] = selected[..] {
+        eprintln!("error: options '{}' and '{}' can not be used together", first, second);
+        process::exit(2);
+    }
+
+    for (option, modes) in MODE_OPTIONS {
+        if options.flag(option) && !modes.iter().any(|mode| options.flag(mode)) {
+            eprintln!("error: option '{}' requires '{}'", option, modes.join("' or '"));
+            process::exit(2);
         }
+    }
+}
+
+fn print_stats(options: &Options, config: &FileConfig, sonar_data: Vec<Depth>) {
+    let bucket_width = positive_option(options, "--histogram-width", DEFAULT_HISTOGRAM_WIDTH);
 
-        previous_measure = *measure;
+    match SonarStats::from_data(&sonar_data, bucket_width) {
+        Ok(stats) => println!("{}", stats.render(options.format)),
+        Err(e) => analysis_failed(options, config, e)
     }
+}
+
+fn print_window_table<I>(options: &Options, config: &FileConfig, windows: &str, sonar_data: I)
+    where I: Iterator<Item = Depth>
+{
+    let window_sizes = windows.split(',')
+        .map(|size| size.trim().parse::<usize>().ok().filter(|size| *size > 0))
+        .collect::<Option<Vec<usize>>>()
+        .unwrap_or_else(|| {
+            eprintln!("error: --windows expects comma separated positive integers, found '{}'", windows);
+            process::exit(2);
+        });
 
-    depth_increase_count
+    let rows = multi_window_breakdowns(sonar_data, &window_sizes).unwrap_or_else(|e| analysis_failed(options, config, e));
+    println!("{}", render_window_table(&rows, options.format));
 }
 
-fn load_data(path: &str) -> Vec<i32> {
-    let file = File::open(path)
-        .expect("Error loading file");
-    let reader = BufReader::new(file);
-
-    let mut data: Vec<i32> = Vec::new();
-    for line in reader.lines() {
-        if let Ok(d) = line {
-            data.push(d.trim()
-                .parse()
-                .expect("Unable to parse input to integer")
-            );
-        };
+fn plot_profile(options: &Options, config: &FileConfig, sonar_data: Vec<Depth>) {
+    let window_size = positive_option(options, "--plot-window", 1);
+    let columns = positive_option(options, "--plot-width", DEFAULT_PLOT_WIDTH);
+    let series = profile_series(&sonar_data, window_size, columns).unwrap_or_else(|e| analysis_failed(options, config, e));
+
+    if options.flag("--plot") {
+        println!("{}", render_terminal(&series, PLOT_HEIGHT, io::stdout().is_terminal()));
     }
 
-    data
+    if let Some(path) = options.value("--svg") {
+        let (width, height) = SVG_SIZE;
+        if let Err(e) = fs::write(path, render_svg(&series, width, height)) {
+            eprintln!("Unable to write {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn analysis_failed(options: &Options, config: &FileConfig, error: AnalysisError) -> ! {
+    eprintln!("{}: {}", options.input_name(config), error);
+    process::exit(1);
 }
 
-fn get_input_file_path(env: Option<String>) -> &'static str {
-    match env {
-        Some(s) => if s == "test" {TEST_INPUT_DATA_FILE} else {INPUT_DATA_FILE},
-        _ => INPUT_DATA_FILE
+/// Value of a positive integer option, exiting with usage error status when invalid
+fn positive_option<T: FromStr + PartialOrd + Default>(options: &Options, name: &str, default: T) -> T {
+    match options.value(name).map(str::parse::<T>) {
+        None => default,
+        Some(Ok(value)) if value > T::default() => value,
+        Some(_) => {
+            eprintln!("error: {} must be a positive integer", name);
+            process::exit(2);
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Read sonar measurements, exiting with a diagnostic on errors
+///
+/// Strict reading is lazy and fails on the first malformed line, lenient policies load the whole input and
+/// report affected readings on stderr.
+fn load_data(options: &Options, config: &FileConfig) -> Box<dyn Iterator<Item = Depth>> {
+    let policy = match options.value("--on-invalid").map(str::parse::<ReadingPolicy>) {
+        None => ReadingPolicy::Strict,
+        Some(Ok(policy)) => policy,
+        Some(Err(e)) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    };
+
+    let lines = options.lines(config).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+
+    if policy == ReadingPolicy::Strict {
+        return Box::new(sonar_readings(lines).map(|measure| measure.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        })));
+    }
+
+    let sonar = read_sonar_data(lines, policy).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    if !sonar.malformed.is_empty() {
+        eprintln!("{}: {}", options.input_name(config), sonar);
+    }
+
+    Box::new(sonar.readings.into_iter())
+}
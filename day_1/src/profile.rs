@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+
+use crate::sonar::{AnalysisError, Depth, require_data, require_window};
+use crate::window::rolling_windows;
+
+const INCREASE_COLOR: &str = "\x1b[31m";
+const RESET_COLOR: &str = "\x1b[0m";
+const SVG_MARGIN: f64 = 20.0;
+
+/// Depths to plot, optionally smoothed with the mean of `window_size` measurements and downsampled
+/// to at most `columns` points
+///
+/// Fails when `window_size` is 0 or there is not a full window of measurements.
+pub fn profile_series(sonar_data: &[Depth], window_size: usize, columns: usize) -> Result<Vec<f64>, AnalysisError> {
+    require_window(window_size)?;
+    require_data(window_size, sonar_data.len())?;
+
+    let series: Vec<f64> = rolling_windows(sonar_data.iter().copied(), window_size)
+        .map(|window| window.mean())
+        .collect();
+
+    Ok(downsample(&series, columns))
+}
+
+/// Average consecutive values into `columns` buckets, series shorter than `columns` are kept as is
+pub fn downsample(series: &[f64], columns: usize) -> Vec<f64> {
+    if columns == 0 || series.len() <= columns { return series.to_vec() }
+
+    (0..columns)
+        .map(|col| {
+            let bucket = &series[col * series.len() / columns..(col + 1) * series.len() / columns];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+/// Plot depths as a terminal chart of `height` rows, deeper values are drawn lower
+///
+/// Points deeper than the previous one are drawn with `#`, and in red when `color` is set, others
+/// with `*`. The seabed below each point is filled with `:`.
+pub fn render_terminal(series: &[f64], height: usize, color: bool) -> String {
+    if series.is_empty() || height == 0 { return String::new() }
+
+    let (min, max) = bounds(series);
+    let level = |row: usize| if height == 1 { min } else { min + (max - min) * row as f64 / (height - 1) as f64 };
+    let row_of = |value: f64| if max == min { 0 } else { ((value - min) / (max - min) * (height - 1) as f64).round() as usize };
+
+    let mut lines = Vec::with_capacity(height + 1);
+    for row in 0..height {
+        let label = if row == 0 || row == height - 1 { format!("{:>8.0}", level(row)) } else { " ".repeat(8) };
+        let mut line = format!("{} |", label);
+        for (idx, value) in series.iter().enumerate() {
+            let value_row = row_of(*value);
+            let increase = idx > 0 && *value > series[idx - 1];
+            match value_row.cmp(&row) {
+                Ordering::Equal if increase && color => line.push_str(&format!("{}#{}", INCREASE_COLOR, RESET_COLOR)),
+                Ordering::Equal if increase => line.push('#'),
+                Ordering::Equal => line.push('*'),
+                Ordering::Less => line.push(':'),
+                Ordering::Greater => line.push(' ')
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.push(format!("{} +{}", " ".repeat(8), "-".repeat(series.len())));
+
+    lines.join("\n")
+}
+
+/// Plot depths as an SVG line chart of `width`x`height` pixels, steps going deeper are drawn in red
+pub fn render_svg(series: &[f64], width: u32, height: u32) -> String {
+    let (min, max) = bounds(series);
+    let plot_width = width as f64 - 2.0 * SVG_MARGIN;
+    let plot_height = height as f64 - 2.0 * SVG_MARGIN;
+    let x = |idx: usize| SVG_MARGIN + if series.len() > 1 { plot_width * idx as f64 / (series.len() - 1) as f64 } else { 0.0 };
+    let y = |value: f64| SVG_MARGIN + if max > min { plot_height * (value - min) / (max - min) } else { 0.0 };
+
+    let mut elements = vec![
+        format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">", width, height, width, height),
+        format!("<rect width=\"{}\" height=\"{}\" fill=\"white\"/>", width, height),
+        format!("<text x=\"{}\" y=\"14\" font-size=\"12\" font-family=\"monospace\">depth {:.0} - {:.0}</text>", SVG_MARGIN, min, max)
+    ];
+    for idx in 1..series.len() {
+        let stroke = if series[idx] > series[idx - 1] { "crimson" } else { "steelblue" };
+        elements.push(format!(
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+            x(idx - 1), y(series[idx - 1]), x(idx), y(series[idx]), stroke
+        ));
+    }
+    elements.push("</svg>".to_string());
+
+    elements.join("\n")
+}
+
+fn bounds(series: &[f64]) -> (f64, f64) {
+    if series.is_empty() { return (0.0, 0.0) }
+
+    series.iter().fold((f64::MAX, f64::MIN), |(min, max), value| (min.min(*value), max.max(*value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sonar::AnalysisError;
+    use super::{downsample, profile_series, render_svg, render_terminal};
+
+    #[test]
+    fn should_downsample_series() {
+        assert_eq!(vec![1.5, 3.5], downsample(&[1.0, 2.0, 3.0, 4.0], 2));
+        assert_eq!(vec![1.0, 2.0], downsample(&[1.0, 2.0], 5));
+    }
+
+    #[test]
+    fn should_smooth_with_window_mean() {
+        assert_eq!(Ok(vec![2.0, 3.0]), profile_series(&[1, 2, 3, 4], 3, 80));
+        assert_eq!(Ok(vec![2.0]), profile_series(&[1, 2, 3], 3, 80));
+    }
+
+    #[test]
+    fn should_fail_without_full_window() {
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 1, found: 0 }), profile_series(&[], 1, 80));
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 3, found: 2 }), profile_series(&[1, 2], 3, 80));
+        assert_eq!(Err(AnalysisError::EmptyWindow), profile_series(&[1, 2], 0, 80));
+    }
+
+    #[test]
+    fn should_render_terminal_profile() {
+        let chart = render_terminal(&[0.0, 2.0, 1.0, 2.0], 3, false);
+
+        assert_eq!(
+            "       0 |*\n         |: *\n       2 |:#:#\n         +----",
+            chart
+        );
+    }
+
+    #[test]
+    fn should_highlight_increases_in_svg() {
+        let svg = render_svg(&[0.0, 2.0, 1.0, 2.0], 200, 100);
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(2, svg.matches("crimson").count());
+        assert_eq!(1, svg.matches("steelblue").count());
+    }
+}
@@ -0,0 +1,92 @@
+use std::error::Error;
+
+use common::{Answer, Day, Solution};
+
+use crate::compare::{
+    ChangeBreakdown, ChangePolicy, multi_window_breakdowns, single_measurement_breakdown, windowed_measurement_breakdown
+};
+use crate::sonar::{AnalysisError, Depth, parse_sonar_data};
+
+/// Measurements summed by part two windows
+const WINDOW_SIZE: usize = 3;
+
+/// Day registration used by the runners
+pub fn day() -> Day {
+    Day::new::<SonarSweep>(1, "Sonar Sweep", "./day_1")
+}
+
+/// Sonar sweep puzzle solution
+pub struct SonarSweep {
+    sonar_data: Vec<Depth>
+}
+
+impl Solution for SonarSweep {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(SonarSweep { sonar_data: parse_sonar_data(input)? })
+    }
+
+    fn part_one(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_one_answer(self.sonar_data.iter().copied())?)
+    }
+
+    fn part_two(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_two_answer(self.sonar_data.iter().copied())?)
+    }
+}
+
+/// Number of measurements larger than the previous one, fails with less than two measurements
+pub fn part_one_answer<I: IntoIterator<Item = Depth>>(sonar_data: I) -> Result<Answer, AnalysisError> {
+    single_measurement_breakdown(sonar_data, &ChangePolicy::Strict)
+        .map(|breakdown| breakdown_answer("depth_increases", breakdown))
+}
+
+/// Number of three-measurement window sums larger than the previous one, fails with less than four measurements
+pub fn part_two_answer<I: IntoIterator<Item = Depth>>(sonar_data: I) -> Result<Answer, AnalysisError> {
+    windowed_measurement_breakdown(sonar_data, WINDOW_SIZE, &ChangePolicy::Strict)
+        .map(|breakdown| breakdown_answer("windowed_depth_increases", breakdown))
+}
+
+/// Answers of both parts in a single traversal keeping only the last measurements, see [`multi_window_breakdowns`]
+pub fn both_answers<I: IntoIterator<Item = Depth>>(sonar_data: I) -> Result<(Answer, Answer), AnalysisError> {
+    let rows = multi_window_breakdowns(sonar_data, &[1, WINDOW_SIZE])?;
+
+    Ok((
+        breakdown_answer("depth_increases", rows[0].breakdown?),
+        breakdown_answer("windowed_depth_increases", rows[1].breakdown?)
+    ))
+}
+
+fn breakdown_answer(name: &'static str, breakdown: ChangeBreakdown) -> Answer {
+    Answer::new(name, breakdown.increases)
+        .with("decreases", breakdown.decreases)
+        .with("equal", breakdown.equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Part, run_day};
+
+    use crate::fixtures::EXAMPLE;
+    use crate::sonar::AnalysisError;
+    use super::{both_answers, day, part_one_answer, part_two_answer};
+
+    #[test]
+    fn should_fail_without_enough_data() {
+        assert!(run_day(&day(), "", &Part::ALL).is_err());
+        assert!(run_day(&day(), "1\n2\n3", &Part::ALL).is_err());
+        assert_eq!("2", run_day(&day(), "1\n2\n3", &[Part::One]).unwrap().parts[0].answer.to_string());
+        assert_eq!("1", run_day(&day(), "1\n2\n3\n4", &Part::ALL).unwrap().parts[1].answer.to_string());
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 1 }), part_one_answer([1]).map(|a| a.to_string()));
+        assert_eq!(Ok("0".to_string()), part_two_answer([1, 1, 1, 1]).map(|a| a.to_string()));
+    }
+
+    #[test]
+    fn should_answer_both_parts_in_one_pass() {
+        let (one, two) = both_answers(EXAMPLE).unwrap();
+
+        assert_eq!(part_one_answer(EXAMPLE).unwrap(), one);
+        assert_eq!(part_two_answer(EXAMPLE).unwrap(), two);
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 1 }), both_answers([1]).map(|_| ()));
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 4, found: 3 }), both_answers([1, 2, 3]).map(|_| ()));
+    }
+}
@@ -0,0 +1,296 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use common::{InputError, Lines, ParsedLines, load_data_with_type_from_str, try_load_data_with_type};
+
+use crate::compare::{ChangePolicy, single_measurement_breakdown, windowed_measurement_breakdown};
+
+/// Sonar depth measurement
+pub type Depth = i32;
+
+/// Error raised when a sonar analysis can not be computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisError {
+    /// Fewer measurements than the analysis needs, e.g. a single one when comparing consecutive ones
+    NotEnoughData { required: usize, found: usize },
+    /// Window size of 0 was requested
+    EmptyWindow
+}
+
+impl Display for AnalysisError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::NotEnoughData { required, found } =>
+                write!(f, "not enough sonar data, {} measurements required but {} found", required, found),
+            AnalysisError::EmptyWindow => write!(f, "window size must be greater than 0")
+        }
+    }
+}
+
+impl Error for AnalysisError {}
+
+/// Fail with `NotEnoughData` when `found` measurements are less than `required`
+pub(crate) fn require_data(required: usize, found: usize) -> Result<(), AnalysisError> {
+    if found < required { Err(AnalysisError::NotEnoughData { required, found }) } else { Ok(()) }
+}
+
+pub(crate) fn require_window(window_size: usize) -> Result<(), AnalysisError> {
+    if window_size == 0 { Err(AnalysisError::EmptyWindow) } else { Ok(()) }
+}
+
+/// Load sonar measurements from a file, one per line, `-` reads standard input
+pub fn load_sonar_data(path: &str) -> Result<Vec<Depth>, InputError<ParseIntError>> {
+    try_load_data_with_type(path)
+}
+
+/// Parse in-memory sonar measurements, one per line
+pub fn parse_sonar_data(input: &str) -> Result<Vec<Depth>, InputError<ParseIntError>> {
+    load_data_with_type_from_str(input)
+}
+
+/// Lazily parse sonar measurements so inputs of any size can be analysed with constant memory
+pub fn sonar_readings<R: BufRead>(lines: Lines<R>) -> ParsedLines<Depth, R> {
+    ParsedLines::new(lines)
+}
+
+/// How malformed or blank readings are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingPolicy {
+    /// Fail on the first malformed reading
+    Strict,
+    /// Drop malformed readings
+    Skip,
+    /// Replace malformed readings by linear interpolation of the surrounding valid ones
+    Interpolate
+}
+
+impl FromStr for ReadingPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(ReadingPolicy::Strict),
+            "skip" => Ok(ReadingPolicy::Skip),
+            "interpolate" => Ok(ReadingPolicy::Interpolate),
+            unk => Err(format!("unknown reading policy '{}'", unk))
+        }
+    }
+}
+
+/// Reading that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedReading {
+    /// 1-based line number
+    pub line: usize,
+    pub text: String,
+    /// Interpolated depth, `None` when the reading was dropped
+    pub replacement: Option<Depth>
+}
+
+/// Sonar measurements loaded with a lenient `ReadingPolicy`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SonarReadings {
+    pub readings: Vec<Depth>,
+    pub malformed: Vec<MalformedReading>
+}
+
+impl SonarReadings {
+    /// Interpolate pending malformed readings between `from` and `to` depths
+    fn fill_gap(&mut self, pending: &mut Vec<(usize, String)>, from: Depth, to: Depth) {
+        let steps = pending.len() as f64 + 1.0;
+        for (idx, (line, text)) in pending.drain(..).enumerate() {
+            let depth = from + ((to - from) as f64 * (idx + 1) as f64 / steps).round() as Depth;
+            self.readings.push(depth);
+            self.malformed.push(MalformedReading { line, text, replacement: Some(depth) });
+        }
+    }
+}
+
+impl Display for SonarReadings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let replaced = self.malformed.iter().filter(|m| m.replacement.is_some()).count();
+        write!(
+            f,
+            "{} malformed readings ({} interpolated, {} skipped)",
+            self.malformed.len(),
+            replaced,
+            self.malformed.len() - replaced
+        )?;
+        for reading in &self.malformed {
+            write!(f, "\n  line {}: '{}'", reading.line, reading.text)?;
+            if let Some(depth) = reading.replacement {
+                write!(f, " -> {}", depth)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Load sonar measurements handling malformed and blank readings according to `policy`
+///
+/// Strict policy fails with the parse error of the first malformed reading. With interpolation, malformed
+/// readings before the first or after the last valid one take its value, and are dropped when there is no
+/// valid reading at all. Read errors are always returned.
+pub fn read_sonar_data<R: BufRead>(
+    lines: Lines<R>,
+    policy: ReadingPolicy
+) -> Result<SonarReadings, InputError<ParseIntError>> {
+    let mut sonar = SonarReadings::default();
+    // Malformed readings waiting for the next valid one to be interpolated
+    let mut pending: Vec<(usize, String)> = Vec::new();
+    for reading in sonar_readings(lines) {
+        match reading {
+            Ok(depth) => {
+                let previous = sonar.readings.last().copied().unwrap_or(depth);
+                sonar.fill_gap(&mut pending, previous, depth);
+                sonar.readings.push(depth);
+            },
+            Err(InputError::Parse { line, text, .. }) if policy == ReadingPolicy::Skip => {
+                sonar.malformed.push(MalformedReading { line, text, replacement: None });
+            },
+            Err(InputError::Parse { line, text, .. }) if policy == ReadingPolicy::Interpolate => {
+                pending.push((line, text));
+            },
+            Err(e) => return Err(e)
+        }
+    }
+
+    match sonar.readings.last().copied() {
+        Some(last) => sonar.fill_gap(&mut pending, last, last),
+        None => sonar.malformed.extend(pending.into_iter()
+            .map(|(line, text)| MalformedReading { line, text, replacement: None }))
+    }
+
+    Ok(sonar)
+}
+
+/// Count sums of `window_size` consecutive measurements larger than the previous sum
+///
+/// Runs in linear time whatever the window size. Fails when there are not two windows to compare, i.e.
+/// less than `window_size + 1` measurements, or when `window_size` is 0.
+/// See `compare::windowed_measurement_breakdown` for decreases and other comparison policies.
+pub fn windowed_measurement_depth_increase_count<I>(sonar_data: I, window_size: usize) -> Result<i32, AnalysisError>
+    where I: IntoIterator<Item = Depth>
+{
+    windowed_measurement_breakdown(sonar_data, window_size, &ChangePolicy::Strict)
+        .map(|breakdown| breakdown.increases as i32)
+}
+
+/// Count measurements larger than the previous one
+///
+/// Fails with less than two measurements, as there is nothing to compare.
+/// See `compare::single_measurement_breakdown` for decreases and other comparison policies.
+pub fn single_measurement_depth_increase_count<I>(sonar_data: I) -> Result<i32, AnalysisError>
+    where I: IntoIterator<Item = Depth>
+{
+    single_measurement_breakdown(sonar_data, &ChangePolicy::Strict).map(|breakdown| breakdown.increases as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{InputError, Lines};
+
+    use crate::fixtures::EXAMPLE;
+    use super::{
+        AnalysisError, MalformedReading, ReadingPolicy, parse_sonar_data, read_sonar_data,
+        single_measurement_depth_increase_count, sonar_readings, windowed_measurement_depth_increase_count
+    };
+
+    #[test]
+    fn should_count_single_measurement_increases() {
+        assert_eq!(Ok(7), single_measurement_depth_increase_count(EXAMPLE));
+        assert_eq!(Ok(0), single_measurement_depth_increase_count([2, 1]));
+    }
+
+    #[test]
+    fn should_count_windowed_measurement_increases() {
+        assert_eq!(Ok(5), windowed_measurement_depth_increase_count(EXAMPLE, 3));
+        assert_eq!(Ok(7), windowed_measurement_depth_increase_count(EXAMPLE, 1));
+        assert_eq!(Ok(1), windowed_measurement_depth_increase_count([1, 2, 3, 4], 3));
+    }
+
+    #[test]
+    fn should_fail_single_count_without_two_measurements() {
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 2, found: 0 }),
+            single_measurement_depth_increase_count([])
+        );
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 2, found: 1 }),
+            single_measurement_depth_increase_count([199])
+        );
+    }
+
+    #[test]
+    fn should_fail_windowed_count_without_two_windows() {
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 4, found: 0 }),
+            windowed_measurement_depth_increase_count([], 3)
+        );
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 4, found: 1 }),
+            windowed_measurement_depth_increase_count([199], 3)
+        );
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 4, found: 3 }),
+            windowed_measurement_depth_increase_count([199, 200, 208], 3)
+        );
+        assert_eq!(Err(AnalysisError::EmptyWindow), windowed_measurement_depth_increase_count(EXAMPLE, 0));
+    }
+
+    #[test]
+    fn should_parse_sonar_data() {
+        assert_eq!(vec![199, 200, 208], parse_sonar_data("199\n200\n 208 \n").unwrap());
+
+        let error = parse_sonar_data("199\nabc\n").unwrap_err();
+        assert!(matches!(error, InputError::Parse { line: 2, .. }));
+    }
+
+    #[test]
+    fn should_stream_sonar_readings() {
+        let readings = sonar_readings(Lines::new("<test>", "199\n200\n208\n".as_bytes()));
+
+        assert_eq!(Ok(2), single_measurement_depth_increase_count(readings.map(Result::unwrap)));
+    }
+
+    #[test]
+    fn should_fail_on_malformed_reading_in_strict_mode() {
+        let error = read_sonar_data(Lines::new("<test>", "199\n\n208\n".as_bytes()), ReadingPolicy::Strict).unwrap_err();
+
+        assert!(matches!(error, InputError::Parse { line: 2, .. }));
+    }
+
+    #[test]
+    fn should_skip_malformed_readings() {
+        let sonar = read_sonar_data(Lines::new("<test>", "199\nabc\n208\n\n210".as_bytes()), ReadingPolicy::Skip).unwrap();
+
+        assert_eq!(vec![199, 208, 210], sonar.readings);
+        assert_eq!(vec![2, 4], sonar.malformed.iter().map(|m| m.line).collect::<Vec<_>>());
+        assert_eq!(None, sonar.malformed[0].replacement);
+    }
+
+    #[test]
+    fn should_interpolate_malformed_readings() {
+        let input = "x\n100\n?\n\n106\n200\n-";
+        let sonar = read_sonar_data(Lines::new("<test>", input.as_bytes()), ReadingPolicy::Interpolate).unwrap();
+
+        assert_eq!(vec![100, 100, 102, 104, 106, 200, 200], sonar.readings);
+        assert_eq!(
+            MalformedReading { line: 3, text: "?".to_string(), replacement: Some(102) },
+            sonar.malformed[1]
+        );
+        assert_eq!(4, sonar.malformed.len());
+    }
+
+    #[test]
+    fn should_drop_readings_without_valid_neighbours() {
+        let sonar = read_sonar_data(Lines::new("<test>", "x\ny".as_bytes()), ReadingPolicy::Interpolate).unwrap();
+
+        assert!(sonar.readings.is_empty());
+        assert_eq!(2, sonar.malformed.len());
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use common::Format;
+
+use crate::sonar::{AnalysisError, Depth, require_data};
+
+/// Descriptive statistics of a sonar series
+#[derive(Debug, Clone, PartialEq)]
+pub struct SonarStats {
+    pub count: usize,
+    pub min_depth: Depth,
+    /// Position of the first minimum reading starting by 0
+    pub min_index: usize,
+    pub max_depth: Depth,
+    /// Position of the first maximum reading starting by 0
+    pub max_index: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    /// Longest run of consecutive increasing steps
+    pub longest_increase_run: usize,
+    /// Longest run of consecutive decreasing steps
+    pub longest_decrease_run: usize,
+    /// Width of the step histogram buckets
+    pub bucket_width: i64,
+    /// Number of steps by bucket lower bound, bucket `b` holds steps in `[b, b + bucket_width)`
+    pub step_histogram: BTreeMap<i64, usize>
+}
+
+impl SonarStats {
+    /// Compute statistics, fails for an empty series
+    ///
+    /// A single reading has no steps, so its runs are 0 and its histogram is empty.
+    ///
+    /// # Panics
+    /// If `bucket_width` is not positive.
+    pub fn from_data(sonar_data: &[Depth], bucket_width: i64) -> Result<SonarStats, AnalysisError> {
+        assert!(bucket_width > 0, "bucket width must be greater than 0");
+        require_data(1, sonar_data.len())?;
+
+        let count = sonar_data.len();
+        let (min_index, min_depth) = sonar_data.iter().copied().enumerate()
+            .min_by_key(|(idx, depth)| (*depth, *idx))
+            .unwrap_or_default();
+        let (max_index, max_depth) = sonar_data.iter().copied().enumerate()
+            .max_by_key(|(idx, depth)| (*depth, -(*idx as i64)))
+            .unwrap_or_default();
+
+        let mean = sonar_data.iter().map(|d| *d as f64).sum::<f64>() / count as f64;
+        let variance = sonar_data.iter().map(|d| (*d as f64 - mean).powi(2)).sum::<f64>() / count as f64;
+
+        let mut sorted = sonar_data.to_vec();
+        sorted.sort_unstable();
+        let mid = count / 2;
+        let median = if count.is_multiple_of(2) { (sorted[mid - 1] as f64 + sorted[mid] as f64) / 2.0 } else { sorted[mid] as f64 };
+
+        let mut longest_increase_run = 0;
+        let mut longest_decrease_run = 0;
+        let (mut increase_run, mut decrease_run) = (0, 0);
+        let mut step_histogram = BTreeMap::new();
+        for pair in sonar_data.windows(2) {
+            let step = pair[1] as i64 - pair[0] as i64;
+            increase_run = if step > 0 { increase_run + 1 } else { 0 };
+            decrease_run = if step < 0 { decrease_run + 1 } else { 0 };
+            longest_increase_run = longest_increase_run.max(increase_run);
+            longest_decrease_run = longest_decrease_run.max(decrease_run);
+
+            *step_histogram.entry(step.div_euclid(bucket_width) * bucket_width).or_insert(0) += 1;
+        }
+
+        Ok(SonarStats {
+            count,
+            min_depth,
+            min_index,
+            max_depth,
+            max_index,
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+            longest_increase_run,
+            longest_decrease_run,
+            bucket_width,
+            step_histogram
+        })
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.to_text(),
+            Format::Json => self.to_json(),
+            Format::Tsv => self.to_tsv()
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("Sonar statistics ({} readings)", self.count),
+            format!("  Min depth: {} (reading {})", self.min_depth, self.min_index),
+            format!("  Max depth: {} (reading {})", self.max_depth, self.max_index),
+            format!("  Mean: {:.2}", self.mean),
+            format!("  Median: {:.1}", self.median),
+            format!("  Standard deviation: {:.2}", self.std_dev),
+            format!("  Longest increase run: {}", self.longest_increase_run),
+            format!("  Longest decrease run: {}", self.longest_decrease_run),
+            "  Step histogram:".to_string()
+        ];
+
+        let max_count = self.step_histogram.values().copied().max().unwrap_or(0);
+        let bar_scale = max_count.div_ceil(40).max(1);
+        for (bucket, count) in &self.step_histogram {
+            let range = format!("[{}, {})", bucket, bucket + self.bucket_width);
+            lines.push(format!("    {:>14} {:>6} {}", range, count, "#".repeat(count.div_ceil(bar_scale))));
+        }
+
+        lines.join("\n")
+    }
+
+    fn to_json(&self) -> String {
+        let buckets: Vec<String> = self.step_histogram.iter()
+            .map(|(bucket, count)| format!("{{\"from\":{},\"to\":{},\"count\":{}}}", bucket, bucket + self.bucket_width, count))
+            .collect();
+
+        format!(
+            "{{\"count\":{},\"min_depth\":{},\"min_index\":{},\"max_depth\":{},\"max_index\":{},\"mean\":{},\"median\":{},\
+            \"std_dev\":{},\"longest_increase_run\":{},\"longest_decrease_run\":{},\"step_histogram\":[{}]}}",
+            self.count,
+            self.min_depth,
+            self.min_index,
+            self.max_depth,
+            self.max_index,
+            self.mean,
+            self.median,
+            self.std_dev,
+            self.longest_increase_run,
+            self.longest_decrease_run,
+            buckets.join(",")
+        )
+    }
+
+    fn to_tsv(&self) -> String {
+        let mut rows = vec![
+            "statistic\tvalue".to_string(),
+            format!("count\t{}", self.count),
+            format!("min_depth\t{}", self.min_depth),
+            format!("min_index\t{}", self.min_index),
+            format!("max_depth\t{}", self.max_depth),
+            format!("max_index\t{}", self.max_index),
+            format!("mean\t{}", self.mean),
+            format!("median\t{}", self.median),
+            format!("std_dev\t{}", self.std_dev),
+            format!("longest_increase_run\t{}", self.longest_increase_run),
+            format!("longest_decrease_run\t{}", self.longest_decrease_run)
+        ];
+        for (bucket, count) in &self.step_histogram {
+            rows.push(format!("steps[{},{})\t{}", bucket, bucket + self.bucket_width, count));
+        }
+
+        rows.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Format;
+
+    use crate::fixtures::EXAMPLE;
+    use crate::sonar::AnalysisError;
+    use super::SonarStats;
+
+    #[test]
+    fn should_compute_sonar_stats() {
+        let stats = SonarStats::from_data(&EXAMPLE, 10).unwrap();
+
+        assert_eq!(10, stats.count);
+        assert_eq!((199, 0), (stats.min_depth, stats.min_index));
+        assert_eq!((269, 7), (stats.max_depth, stats.max_index));
+        assert!((stats.mean - 225.6).abs() < 1e-9);
+        assert!((stats.median - 209.0).abs() < 1e-9);
+        assert!((stats.std_dev - 27.55).abs() < 0.01);
+        assert_eq!(3, stats.longest_increase_run);
+        assert_eq!(1, stats.longest_decrease_run);
+    }
+
+    #[test]
+    fn should_bucket_steps() {
+        let stats = SonarStats::from_data(&EXAMPLE, 10).unwrap();
+
+        let histogram: Vec<(i64, usize)> = stats.step_histogram.into_iter().collect();
+        assert_eq!(vec![(-10, 2), (0, 5), (20, 1), (30, 1)], histogram);
+    }
+
+    #[test]
+    fn should_handle_short_series() {
+        let single = SonarStats::from_data(&[5], 10).unwrap();
+
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 1, found: 0 }), SonarStats::from_data(&[], 10));
+        assert_eq!((5, 5.0), (single.max_depth, single.median));
+        assert_eq!(0, single.longest_increase_run);
+        assert!(single.step_histogram.is_empty());
+    }
+
+    #[test]
+    fn should_render_json() {
+        let json = SonarStats::from_data(&[1, 3], 5).unwrap().render(Format::Json);
+
+        assert_eq!(
+            "{\"count\":2,\"min_depth\":1,\"min_index\":0,\"max_depth\":3,\"max_index\":1,\"mean\":2,\"median\":2,\
+            \"std_dev\":1,\"longest_increase_run\":1,\"longest_decrease_run\":0,\"step_histogram\":[{\"from\":0,\"to\":5,\"count\":1}]}",
+            json
+        );
+    }
+}
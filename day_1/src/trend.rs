@@ -0,0 +1,147 @@
+use crate::compare::{Change, Comparison};
+use crate::sonar::{AnalysisError, Depth, require_window};
+use crate::window::rolling_windows;
+
+/// Run of consecutive steps in the same direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    /// Index of the first value of the segment
+    pub start: usize,
+    /// Index of the last value of the segment, shared with the start of the next segment
+    pub end: usize,
+    pub direction: Change,
+    /// Difference between the last and first values
+    pub change: i64
+}
+
+impl Segment {
+    /// Number of steps in the segment
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Split values into ascending, descending and flat segments as classified by `comparison`
+///
+/// Fails with less than two values, as there is no step to classify.
+pub fn segments<I, C>(values: I, comparison: &C) -> Result<Vec<Segment>, AnalysisError>
+    where I: IntoIterator<Item = i64>, C: Comparison + ?Sized
+{
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut values = values.into_iter();
+    let mut previous = match values.next() {
+        Some(value) => value,
+        None => return Err(AnalysisError::NotEnoughData { required: 2, found: 0 })
+    };
+
+    for (idx, value) in values.enumerate() {
+        let direction = comparison.classify(previous, value);
+        let step = value - previous;
+        match segments.last_mut() {
+            Some(segment) if segment.direction == direction => {
+                segment.end = idx + 1;
+                segment.change += step;
+            },
+            _ => segments.push(Segment { start: idx, end: idx + 1, direction, change: step })
+        }
+
+        previous = value;
+    }
+
+    // A single value has no step
+    if segments.is_empty() { return Err(AnalysisError::NotEnoughData { required: 2, found: 1 }) }
+
+    Ok(segments)
+}
+
+/// Segments of consecutive measurements, fails with less than two measurements
+pub fn sonar_segments<I, C>(sonar_data: I, comparison: &C) -> Result<Vec<Segment>, AnalysisError>
+    where I: IntoIterator<Item = Depth>, C: Comparison + ?Sized
+{
+    segments(sonar_data.into_iter().map(i64::from), comparison)
+}
+
+/// Segments of sums of `window_size` consecutive measurements, indices are window start positions
+///
+/// Fails when `window_size` is 0 or there are less than `window_size + 1` measurements.
+pub fn windowed_sonar_segments<I, C>(
+    sonar_data: I,
+    window_size: usize,
+    comparison: &C
+) -> Result<Vec<Segment>, AnalysisError>
+    where I: IntoIterator<Item = Depth>, C: Comparison + ?Sized
+{
+    require_window(window_size)?;
+
+    let mut count = 0;
+    let sonar_data = sonar_data.into_iter().inspect(|_| count += 1);
+    let segments = segments(rolling_windows(sonar_data, window_size).map(|window| window.sum), comparison);
+
+    segments.map_err(|_| AnalysisError::NotEnoughData { required: window_size + 1, found: count })
+}
+
+/// First of the longest segments in `direction`
+pub fn longest_segment(segments: &[Segment], direction: Change) -> Option<&Segment> {
+    segments.iter()
+        .filter(|segment| segment.direction == direction)
+        .fold(None, |longest: Option<&Segment>, segment| match longest {
+            Some(longest) if longest.len() >= segment.len() => Some(longest),
+            _ => Some(segment)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compare::{Change, ChangePolicy};
+    use crate::fixtures::EXAMPLE;
+    use crate::sonar::AnalysisError;
+    use super::{Segment, longest_segment, sonar_segments, windowed_sonar_segments};
+
+    #[test]
+    fn should_split_series_in_segments() {
+        let segments = sonar_segments([1, 2, 4, 4, 4, 3], &ChangePolicy::Strict).unwrap();
+
+        assert_eq!(vec![
+            Segment { start: 0, end: 2, direction: Change::Increase, change: 3 },
+            Segment { start: 2, end: 4, direction: Change::Equal, change: 0 },
+            Segment { start: 4, end: 5, direction: Change::Decrease, change: -1 }
+        ], segments);
+    }
+
+    #[test]
+    fn should_find_longest_segments() {
+        let segments = sonar_segments(EXAMPLE, &ChangePolicy::Strict).unwrap();
+
+        let ascending = longest_segment(&segments, Change::Increase).unwrap();
+        assert_eq!((0, 3, 11), (ascending.start, ascending.end, ascending.change));
+        assert_eq!(1, longest_segment(&segments, Change::Decrease).unwrap().len());
+        assert_eq!(None, longest_segment(&segments, Change::Equal));
+    }
+
+    #[test]
+    fn should_segment_windowed_sums() {
+        // Sums: 607, 618, 618, 617, 647, 716, 769, 792
+        let segments = windowed_sonar_segments(EXAMPLE, 3, &ChangePolicy::Strict).unwrap();
+
+        assert_eq!(4, segments.len());
+        assert_eq!(Segment { start: 3, end: 7, direction: Change::Increase, change: 175 }, segments[3]);
+    }
+
+    #[test]
+    fn should_fail_on_short_series() {
+        let strict = &ChangePolicy::Strict;
+
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 0 }), sonar_segments([], strict));
+        assert_eq!(Err(AnalysisError::NotEnoughData { required: 2, found: 1 }), sonar_segments([1], strict));
+        assert_eq!(1, sonar_segments([1, 1], strict).unwrap().len());
+        assert_eq!(
+            Err(AnalysisError::NotEnoughData { required: 4, found: 3 }),
+            windowed_sonar_segments([1, 2, 3], 3, strict)
+        );
+        assert_eq!(Err(AnalysisError::EmptyWindow), windowed_sonar_segments([1, 2, 3], 0, strict));
+    }
+}
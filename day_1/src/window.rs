@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+use crate::sonar::Depth;
+
+/// Aggregates of a full window of measurements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowStats {
+    /// Index of the first measurement of the window
+    pub start: usize,
+    pub size: usize,
+    pub sum: i64,
+    /// Sum of squared measurements, used for variance
+    pub sum_squares: i128,
+    pub min: Depth,
+    pub max: Depth
+}
+
+impl WindowStats {
+    pub fn mean(&self) -> f64 {
+        self.sum as f64 / self.size as f64
+    }
+
+    /// Population variance of the window measurements
+    pub fn variance(&self) -> f64 {
+        let size = self.size as i128;
+        let sum = self.sum as i128;
+
+        // Exact integer numerator avoids float cancellation on large depths
+        (size * self.sum_squares - sum * sum) as f64 / (size * size) as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// Iterator over every full window of `size` consecutive measurements
+///
+/// Each step is amortized O(1) whatever the window size: sums are updated with the entering and
+/// leaving measurements and min/max are tracked with monotonic deques of `(index, depth)`.
+pub struct RollingWindows<I> {
+    data: I,
+    size: usize,
+    index: usize,
+    window: VecDeque<Depth>,
+    sum: i64,
+    sum_squares: i128,
+    min: VecDeque<(usize, Depth)>,
+    max: VecDeque<(usize, Depth)>
+}
+
+impl<I: Iterator<Item = Depth>> RollingWindows<I> {
+    fn push(&mut self, depth: Depth) {
+        let index = self.index;
+        self.index += 1;
+
+        self.window.push_back(depth);
+        self.sum += depth as i64;
+        self.sum_squares += (depth as i128).pow(2);
+        if self.window.len() > self.size {
+            let leaving = self.window.pop_front().unwrap_or_default();
+            self.sum -= leaving as i64;
+            self.sum_squares -= (leaving as i128).pow(2);
+        }
+
+        while self.min.back().is_some_and(|(_, d)| *d >= depth) { self.min.pop_back(); }
+        self.min.push_back((index, depth));
+        while self.max.back().is_some_and(|(_, d)| *d <= depth) { self.max.pop_back(); }
+        self.max.push_back((index, depth));
+
+        // Drop extremes that left the window
+        let start = self.index.saturating_sub(self.size);
+        while self.min.front().is_some_and(|(i, _)| *i < start) { self.min.pop_front(); }
+        while self.max.front().is_some_and(|(i, _)| *i < start) { self.max.pop_front(); }
+    }
+}
+
+impl<I: Iterator<Item = Depth>> Iterator for RollingWindows<I> {
+    type Item = WindowStats;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // First window needs `size` measurements, next ones a single one
+        loop {
+            let depth = self.data.next()?;
+            self.push(depth);
+            if self.window.len() == self.size { break }
+        }
+
+        Some(WindowStats {
+            start: self.index - self.size,
+            size: self.size,
+            sum: self.sum,
+            sum_squares: self.sum_squares,
+            min: self.min.front().map(|(_, d)| *d)?,
+            max: self.max.front().map(|(_, d)| *d)?
+        })
+    }
+}
+
+/// Roll a window of `size` measurements over `sonar_data`, yielding nothing when there is not a full window
+///
+/// # Panics
+/// If `size` is 0.
+pub fn rolling_windows<I>(sonar_data: I, size: usize) -> RollingWindows<I::IntoIter>
+    where I: IntoIterator<Item = Depth>
+{
+    assert!(size > 0, "window size must be greater than 0");
+
+    RollingWindows {
+        data: sonar_data.into_iter(),
+        size,
+        index: 0,
+        window: VecDeque::with_capacity(size + 1),
+        sum: 0,
+        sum_squares: 0,
+        min: VecDeque::new(),
+        max: VecDeque::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sonar::Depth;
+    use crate::windowed_measurement_depth_increase_count;
+    use super::rolling_windows;
+
+    #[test]
+    fn should_aggregate_each_window() {
+        let windows: Vec<_> = rolling_windows([3, 1, 4, 1, 5], 3).collect();
+
+        assert_eq!(3, windows.len());
+        assert_eq!((8, 1, 4), (windows[0].sum, windows[0].min, windows[0].max));
+        assert_eq!((6, 1, 4), (windows[1].sum, windows[1].min, windows[1].max));
+        assert_eq!((10, 1, 5), (windows[2].sum, windows[2].min, windows[2].max));
+        assert_eq!(2, windows[2].start);
+        assert!((windows[2].mean() - 10.0 / 3.0).abs() < f64::EPSILON);
+        assert!((windows[2].variance() - 26.0 / 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn should_yield_nothing_without_full_window() {
+        assert_eq!(0, rolling_windows([1, 2], 3).count());
+    }
+
+    #[test]
+    fn should_match_naive_windows() {
+        let data = pseudo_random_depths(500);
+        for size in [1, 2, 3, 7, 50, 499, 500, 501] {
+            let naive: Vec<(i64, Depth, Depth)> = data.windows(size)
+                .map(|w| (w.iter().map(|d| *d as i64).sum(), *w.iter().min().unwrap(), *w.iter().max().unwrap()))
+                .collect();
+            let rolling: Vec<(i64, Depth, Depth)> = rolling_windows(data.iter().copied(), size)
+                .map(|w| (w.sum, w.min, w.max))
+                .collect();
+
+            assert_eq!(naive, rolling, "window size {}", size);
+        }
+    }
+
+    #[test]
+    fn should_match_naive_increase_count() {
+        let data = pseudo_random_depths(500);
+        for size in [1, 2, 3, 10, 100] {
+            let naive_sums: Vec<i32> = data.windows(size).map(|w| w.iter().sum()).collect();
+            let naive_count = naive_sums.windows(2).filter(|pair| pair[1] > pair[0]).count() as i32;
+
+            assert_eq!(Ok(naive_count), windowed_measurement_depth_increase_count(data.iter().copied(), size));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_reject_empty_window() {
+        rolling_windows([1], 0);
+    }
+
+    /// Deterministic depths with plateaus, drops and repeated values
+    fn pseudo_random_depths(len: usize) -> Vec<Depth> {
+        let mut seed: u64 = 42;
+        (0..len)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                ((seed >> 33) % 200) as Depth
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::{self, BufRead, Write};
+
+use common::{STDIN_PATH, lines};
+
+use crate::journal::Journal;
+use crate::submarine::{Command, SubmarineModel};
+use crate::{CourseError, read_commands};
+
+const PROMPT: &str = "> ";
+const HELP: &str = "\
+forward <n>, down <n>, up <n>  move the submarine
+status                         print position, depth and aim
+undo [n]                       undo the last n commands, defaults to 1
+load <path>                    process every command of a course file
+help                           print this help
+quit                           leave the console";
+
+/// Interactive console moving a submarine with course commands
+pub struct Console<S> {
+    journal: Journal<S>
+}
+
+impl<S: SubmarineModel> Console<S> {
+    pub fn new(submarine: S) -> Console<S> {
+        Console { journal: Journal::new(submarine) }
+    }
+
+    pub fn journal(&self) -> &Journal<S> {
+        &self.journal
+    }
+
+    /// Read console lines from `input` until `quit` or end of input, writing replies to `output`
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        write!(output, "{}", PROMPT)?;
+        output.flush()?;
+        for line in input.lines() {
+            match self.execute(&line?) {
+                Some(reply) if reply.is_empty() => (),
+                Some(reply) => writeln!(output, "{}", reply)?,
+                None => return Ok(())
+            }
+            write!(output, "{}", PROMPT)?;
+            output.flush()?;
+        }
+
+        writeln!(output)
+    }
+
+    /// Run a single console line returning its reply, `None` when leaving the console
+    pub fn execute(&mut self, line: &str) -> Option<String> {
+        let mut tokens = line.split_whitespace();
+        let reply = match (tokens.next(), tokens.next(), tokens.next()) {
+            (None, _, _) => String::new(),
+            (Some("quit" | "exit"), None, _) => return None,
+            (Some("help"), None, _) => HELP.to_string(),
+            (Some("status"), None, _) => self.status(),
+            (Some("undo"), steps, None) => match steps.map_or(Ok(1), str::parse::<usize>) {
+                Ok(steps) => format!("undid {} commands, {}", self.journal.undo(steps), self.status()),
+                Err(_) => "error: undo expects a number of commands".to_string()
+            },
+            (Some("load"), Some(_), _) => {
+                // Paths may contain spaces, so the whole rest of the line is used
+                let path = line.trim().strip_prefix("load").unwrap_or_default().trim();
+                self.load(path)
+            },
+            (Some("load"), None, _) => "error: load expects a path".to_string(),
+            _ => match line.parse::<Command>() {
+                Ok(command) => {
+                    self.journal.process_command(command);
+                    self.status()
+                },
+                Err(e) => format!("error: {}, type help to list commands", e)
+            }
+        };
+
+        Some(reply)
+    }
+
+    fn load(&mut self, path: &str) -> String {
+        // Standard input already feeds the console
+        if path == STDIN_PATH { return "error: load can not read standard input".to_string() }
+
+        let commands = lines(path)
+            .map_err(|e| CourseError::from(e.into_parse_error()))
+            .and_then(read_commands);
+
+        match commands {
+            Ok(commands) => {
+                let count = commands.len();
+                for command in commands {
+                    self.journal.process_command(command);
+                }
+                format!("loaded {} commands, {}", count, self.status())
+            },
+            Err(e) => format!("error: nothing loaded\n{}", e)
+        }
+    }
+
+    fn status(&self) -> String {
+        let state = self.journal.submarine().state();
+        format!(
+            "position {}, depth {}, aim {} after {} commands",
+            state.position,
+            state.depth,
+            state.aim,
+            self.journal.commands().count()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::submarine::{AdvancedSubmarine, Submarine};
+    use super::Console;
+
+    #[test]
+    fn should_move_and_undo() {
+        let mut console = Console::new(AdvancedSubmarine::new());
+
+        assert_eq!(Some("position 0, depth 0, aim 5 after 1 commands".to_string()), console.execute("down 5"));
+        assert_eq!(Some("position 8, depth 40, aim 5 after 2 commands".to_string()), console.execute("forward 8"));
+        assert_eq!(Some("undid 1 commands, position 0, depth 0, aim 5 after 1 commands".to_string()), console.execute("undo"));
+        assert_eq!(Some("undid 1 commands, position 0, depth 0, aim 0 after 0 commands".to_string()), console.execute("undo 3"));
+        assert_eq!(None, console.execute("quit"));
+    }
+
+    #[test]
+    fn should_report_invalid_lines() {
+        let mut console = Console::new(Submarine::new());
+
+        assert_eq!(Some("error: unknown movement 'dive', type help to list commands".to_string()), console.execute("dive 3"));
+        assert_eq!(Some("error: undo expects a number of commands".to_string()), console.execute("undo all"));
+        assert_eq!(Some(String::new()), console.execute("  "));
+        assert_eq!(0, console.journal().commands().count());
+    }
+
+    #[test]
+    fn should_load_course_file() {
+        let path = std::env::temp_dir().join(format!("aoc day_2 console {}.txt", std::process::id()));
+        fs::write(&path, "forward 5\ndown 5\nforward 8\n").unwrap();
+        let mut console = Console::new(AdvancedSubmarine::new());
+
+        let reply = console.execute(&format!("load {}", path.display())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!("loaded 3 commands, position 13, depth 40, aim 5 after 3 commands", reply);
+        assert!(console.execute("load ./missing.txt").unwrap().starts_with("error: nothing loaded\nunable to open"));
+        assert_eq!(Some("error: load expects a path".to_string()), console.execute("load"));
+    }
+
+    #[test]
+    fn should_reject_loading_stdin() {
+        let mut console = Console::new(AdvancedSubmarine::new());
+
+        assert_eq!(Some("error: load can not read standard input".to_string()), console.execute("load -"));
+        assert_eq!(0, console.journal().commands().count());
+    }
+
+    #[test]
+    fn should_fail_loading_directory() {
+        let mut console = Console::new(AdvancedSubmarine::new());
+
+        let reply = console.execute(&format!("load {}", std::env::temp_dir().display())).unwrap();
+
+        assert!(reply.starts_with("error: nothing loaded\n"));
+        assert!(reply.contains("unable to read line"));
+        assert_eq!(0, console.journal().commands().count());
+    }
+
+    #[test]
+    fn should_run_session() {
+        let mut console = Console::new(AdvancedSubmarine::new());
+        let mut output = Vec::new();
+
+        console.run("down 2\nstatus\nquit\nforward 1\n".as_bytes(), &mut output).unwrap();
+
+        assert_eq!(
+            "> position 0, depth 0, aim 2 after 1 commands\n> position 0, depth 0, aim 2 after 1 commands\n> ",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use crate::submarine::Command;
+
+/// Course of the puzzle example
+pub const COURSE: [Command; 6] = [
+    Command::FORWARD(5),
+    Command::DOWN(5),
+    Command::FORWARD(8),
+    Command::UP(3),
+    Command::DOWN(8),
+    Command::FORWARD(2)
+];
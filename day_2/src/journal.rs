@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::submarine::{Command, SubmarineModel, SubmarineState};
+
+/// Point of a course a [`Journal`] can be rewound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Number of commands processed when the checkpoint was taken
+    pub index: usize,
+    pub state: SubmarineState
+}
+
+/// Error raised when rewinding to a checkpoint that is no longer part of the journal
+///
+/// Happens when commands were undone past the checkpoint and a different course was followed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleCheckpoint(pub Checkpoint);
+
+impl Display for StaleCheckpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "checkpoint at command {} is not part of the course anymore", self.0.index)
+    }
+}
+
+impl Error for StaleCheckpoint {}
+
+/// Submarine remembering the commands it processed so they can be undone and replayed
+pub struct Journal<S> {
+    submarine: S,
+    /// Processed commands with the state right before each of them
+    history: Vec<(Command, SubmarineState)>
+}
+
+impl<S: SubmarineModel> Journal<S> {
+    pub fn new(submarine: S) -> Journal<S> {
+        Journal { submarine, history: Vec::new() }
+    }
+
+    pub fn submarine(&self) -> &S {
+        &self.submarine
+    }
+
+    /// Commands processed so far, undone ones excluded
+    pub fn commands(&self) -> impl Iterator<Item = Command> + '_ {
+        self.history.iter().map(|(command, _)| *command)
+    }
+
+    pub fn process_command(&mut self, command: Command) {
+        self.history.push((command, self.submarine.state()));
+        self.submarine.process_command(command);
+    }
+
+    /// Undo the last `steps` commands, returning how many were undone
+    pub fn undo(&mut self, steps: usize) -> usize {
+        let steps = steps.min(self.history.len());
+        let undone = self.history.split_off(self.history.len() - steps);
+        if let Some((_, state)) = undone.first() {
+            self.submarine.restore(*state);
+        }
+
+        steps
+    }
+
+    /// Snapshot of the current state
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { index: self.history.len(), state: self.submarine.state() }
+    }
+
+    /// Undo every command processed after `checkpoint`
+    pub fn rewind(&mut self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint> {
+        let state = match checkpoint.index.cmp(&self.history.len()) {
+            Ordering::Less => self.history[checkpoint.index].1,
+            Ordering::Equal => self.submarine.state(),
+            Ordering::Greater => return Err(StaleCheckpoint(checkpoint))
+        };
+        if state != checkpoint.state { return Err(StaleCheckpoint(checkpoint)) }
+
+        self.undo(self.history.len() - checkpoint.index);
+        Ok(())
+    }
+
+    /// Rewind to `checkpoint` and process `commands` from there
+    pub fn replay<I>(&mut self, checkpoint: Checkpoint, commands: I) -> Result<(), StaleCheckpoint>
+        where I: IntoIterator<Item = Command>
+    {
+        self.rewind(checkpoint)?;
+        for command in commands {
+            self.process_command(command);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::COURSE;
+    use crate::submarine::{AdvancedSubmarine, Command, Model, Submarine, SubmarineModel, SubmarineState};
+    use super::{Journal, StaleCheckpoint};
+
+    #[test]
+    fn should_restore_snapshot() {
+        let mut submarine = AdvancedSubmarine::new();
+        submarine.process_commands(&COURSE[..3]);
+        let snapshot = submarine.state();
+
+        submarine.process_commands(&COURSE[3..]);
+        submarine.restore(snapshot);
+
+        assert_eq!(SubmarineState { position: 13, depth: 40, aim: 5 }, submarine.state());
+    }
+
+    #[test]
+    fn should_undo_last_commands() {
+        let mut journal = Journal::new(Submarine::new());
+        for command in COURSE {
+            journal.process_command(command);
+        }
+
+        assert_eq!(2, journal.undo(2));
+        assert_eq!((13, 2), (journal.submarine().get_position(), journal.submarine().get_depth()));
+        assert_eq!(4, journal.undo(10));
+        assert_eq!(SubmarineState::default(), journal.submarine().state());
+        assert_eq!(0, journal.commands().count());
+    }
+
+    #[test]
+    fn should_replay_from_checkpoint() {
+        let mut journal = Journal::new(Model::Aim.submarine());
+        for command in &COURSE[..3] {
+            journal.process_command(*command);
+        }
+        let checkpoint = journal.checkpoint();
+        for command in &COURSE[3..] {
+            journal.process_command(*command);
+        }
+
+        journal.replay(checkpoint, COURSE[3..].iter().copied()).unwrap();
+        assert_eq!((15, 60), (journal.submarine().get_position(), journal.submarine().get_depth()));
+
+        journal.replay(checkpoint, [Command::FORWARD(1)]).unwrap();
+        assert_eq!(
+            vec![Command::FORWARD(5), Command::DOWN(5), Command::FORWARD(8), Command::FORWARD(1)],
+            journal.commands().collect::<Vec<_>>()
+        );
+        assert_eq!(SubmarineState { position: 14, depth: 45, aim: 5 }, journal.submarine().state());
+    }
+
+    #[test]
+    fn should_reject_stale_checkpoint() {
+        let mut journal = Journal::new(AdvancedSubmarine::new());
+        journal.process_command(Command::DOWN(2));
+        journal.process_command(Command::FORWARD(2));
+        let checkpoint = journal.checkpoint();
+
+        journal.undo(2);
+        journal.process_command(Command::UP(1));
+        journal.process_command(Command::FORWARD(2));
+
+        assert_eq!(Err(StaleCheckpoint(checkpoint)), journal.rewind(checkpoint));
+        journal.undo(1);
+        assert_eq!(Err(StaleCheckpoint(checkpoint)), journal.rewind(checkpoint));
+    }
+}
@@ -0,0 +1,121 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+
+use common::{InputError, Lines, ParsedLines};
+
+pub mod console;
+pub mod journal;
+pub mod submarine;
+pub mod trajectory;
+mod solution;
+#[cfg(test)]
+mod fixtures;
+
+pub use solution::{Dive, day, part_one_answer, part_two_answer, position_answer};
+
+use submarine::{Command, CommandError};
+
+const INPUT_NAME: &str = "<input>";
+
+/// Every line of a course that could not be loaded
+#[derive(Debug)]
+pub struct CourseError {
+    pub errors: Vec<InputError<CommandError>>
+}
+
+impl From<InputError<CommandError>> for CourseError {
+    fn from(error: InputError<CommandError>) -> Self {
+        CourseError { errors: vec![error] }
+    }
+}
+
+impl Display for CourseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self.errors.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", errors.join("\n"))
+    }
+}
+
+impl Error for CourseError {}
+
+/// Load course commands, one per line, skipping blank lines
+///
+/// Parsing goes on after a malformed command so that all of them are reported at once, while a
+/// read error stops loading as the reader can not recover from it.
+pub fn read_commands<R: BufRead>(lines: Lines<R>) -> Result<Vec<Command>, CourseError> {
+    let mut commands = Vec::new();
+    let mut errors = Vec::new();
+    for command in ParsedLines::<Command, R>::new(lines) {
+        match command {
+            Ok(command) => commands.push(command),
+            Err(InputError::Parse { text, .. }) if text.is_empty() => (),
+            Err(e @ InputError::Parse { .. }) => errors.push(e),
+            Err(e) => {
+                errors.push(e);
+                return Err(CourseError { errors });
+            }
+        }
+    }
+
+    if errors.is_empty() { Ok(commands) } else { Err(CourseError { errors }) }
+}
+
+/// Parse in-memory course commands, one per line
+pub fn parse_commands(input: &str) -> Result<Vec<Command>, CourseError> {
+    read_commands(Lines::new(INPUT_NAME, input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, Read};
+
+    use common::{InputError, Lines};
+
+    use crate::{parse_commands, read_commands};
+    use crate::submarine::{Command, CommandError};
+
+    #[test]
+    fn should_parse_commands_from_str() {
+        let commands = parse_commands("forward 5\ndown 5\n\nup 3\n").unwrap();
+
+        assert_eq!(vec![Command::FORWARD(5), Command::DOWN(5), Command::UP(3)], commands);
+    }
+
+    #[test]
+    fn should_report_every_malformed_line() {
+        let err = parse_commands("forward 5\nback 2\ndown\nup 3\nforward x\n").unwrap_err();
+
+        let lines: Vec<(usize, &str)> = err.errors.iter()
+            .map(|e| match e {
+                InputError::Parse { line, text, .. } => (*line, text.as_str()),
+                _ => panic!("Expected parse error")
+            })
+            .collect();
+        assert_eq!(vec![(2, "back 2"), (3, "down"), (5, "forward x")], lines);
+        assert!(matches!(err.errors[1], InputError::Parse { source: CommandError::MissingAmount, .. }));
+        assert_eq!(
+            "<input>:2: unable to parse 'back 2': unknown movement 'back'",
+            err.to_string().lines().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_stop_on_read_error() {
+        let lines = Lines::new("failing", BufReader::new(FailingReader));
+
+        let err = read_commands(lines).unwrap_err();
+
+        assert_eq!(1, err.errors.len());
+        assert!(matches!(err.errors[0], InputError::Read { line: 1, .. }));
+    }
+
+    /// Reader failing on every read, as reading a directory does
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("device failure"))
+        }
+    }
+}
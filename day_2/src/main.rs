@@ -1,43 +1,107 @@
-use std::io::{Error, ErrorKind};
+use std::fs;
+use std::io;
+use std::process;
 
-use common::{FileConfig, get_input_file_path, load_data};
-
-mod submarine;
+use common::{Cli, DayReport, FileConfig, InputError, Options, Part};
+use day_2::console::Console;
+use day_2::submarine::{Command, Model};
+use day_2::trajectory::Trajectory;
+use day_2::{CourseError, position_answer, read_commands};
 
 fn main() {
+    let options = Cli::new("day_2")
+        .option("--model", "simple|aim", "Only run the given movement model, simple solves part 1 and aim part 2")
+        .option("--trajectory", "path", "Write the course of the selected model, aim if both are, as CSV to <path> instead of answers")
+        .option("--exceeds", "depth", "Report the first step going deeper than <depth>, requires --trajectory")
+        .flag("--repl", "Move the selected model, aim if both are, with commands typed on standard input")
+        .parse_env();
     let config = FileConfig::default("./day_2");
-    let commands = load_commands_data(get_input_file_path(&config));
+    let day = day_2::day();
+    options.run_shared_modes(&day, &config);
+    if options.flag("--exceeds") && !options.flag("--trajectory") {
+        eprintln!("error: option '--exceeds' requires '--trajectory'");
+        process::exit(2);
+    }
+    let models = selected_models(&options);
+    // Aim model unless only the simple one was selected
+    let model = models.last().map_or(Model::Aim, |(_, model)| *model);
+
+    if options.flag("--repl") {
+        if let Err(e) = Console::new(model.submarine()).run(io::stdin().lock(), io::stdout()) {
+            eprintln!("Console failed: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let commands = match load_commands_data(&options, &config) {
+        Ok(commands) => commands,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
 
-    let mut submarine = submarine::AdvancedSubmarine::new();
+    if let Some(path) = options.value("--trajectory") {
+        write_trajectory(&options, path, model, commands);
+        return;
+    }
+
+    // Every selected model follows the same pass over the course
+    let mut submarines: Vec<_> = models.into_iter().map(|(part, model)| (part, model.submarine())).collect();
     for command in commands {
-        submarine.process_command(command);
+        for (_, submarine) in submarines.iter_mut() {
+            submarine.process_command(command);
+        }
     }
 
-    println!("Final submarine position: {}x, {}y", submarine.get_position(), submarine.get_depth());
-    println!("Depth x position: {}", submarine.get_position() * submarine.get_depth());
+    let mut report = DayReport::new(&day);
+    for (part, submarine) in &submarines {
+        report.add(*part, position_answer(submarine.as_ref()));
+    }
+
+    options.print_report(&report);
 }
 
-fn load_commands_data(path: &str) -> Vec<submarine::Command> {
-    let data = load_data(path);
-    let mut commands: Vec<submarine::Command> = Vec::new();
-    for command_cod in data.iter() {
-        let mut command_data = command_cod.split(" ");
-        let parse_value_f = |cd: &str| {cd.parse().unwrap()};
-        let  command = match command_data.nth(0).expect("Unable to split command data") {
-            "forward" => Ok(submarine::Command::FORWARD(
-                command_data.nth(0).map(parse_value_f).expect("msg")
-            )),
-            "up" => Ok(submarine::Command::UP(
-                command_data.nth(0).map(parse_value_f).expect("msg")
-            )),
-            "down" => Ok(submarine::Command::DOWN(
-                command_data.nth(0).map(parse_value_f).expect("msg")
-            )),
-            unk => Err(Error::new(ErrorKind::InvalidData, format!("Unknown movement: {}", unk)))
-        };
-
-        commands.push(command.unwrap());
+/// Models to run with the part they solve, exiting with usage error status when none is left
+fn selected_models(options: &Options) -> Vec<(Part, Model)> {
+    let model = match options.value("--model").map(str::parse::<Model>) {
+        None => None,
+        Some(Ok(model)) => Some(model),
+        Some(Err(e)) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    };
+
+    let models: Vec<(Part, Model)> = [(Part::One, Model::Simple), (Part::Two, Model::Aim)].into_iter()
+        .filter(|(part, m)| options.runs(*part) && model.is_none_or(|model| model == *m))
+        .collect();
+    if models.is_empty() {
+        eprintln!("error: --model {} does not solve the requested part", options.value("--model").unwrap_or_default());
+        process::exit(2);
     }
 
-    commands
-}
\ No newline at end of file
+    models
+}
+
+fn write_trajectory(options: &Options, path: &str, model: Model, commands: Vec<Command>) {
+    let exceeds = options.value("--exceeds").map(|depth| depth.parse::<i32>().unwrap_or_else(|_| {
+        eprintln!("error: --exceeds must be an integer");
+        process::exit(2);
+    }));
+
+    let trajectory = Trajectory::record(model.submarine().as_mut(), commands);
+    if let Err(e) = fs::write(path, trajectory.to_csv() + "\n") {
+        eprintln!("Unable to write {}: {}", path, e);
+        process::exit(1);
+    }
+
+    println!("{}", trajectory.summary(exceeds).render(options.format));
+}
+
+fn load_commands_data(options: &Options, config: &FileConfig) -> Result<Vec<Command>, CourseError> {
+    let lines = options.lines(config).map_err(InputError::into_parse_error)?;
+
+    read_commands(lines)
+}
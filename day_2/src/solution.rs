@@ -0,0 +1,55 @@
+use std::error::Error;
+
+use common::{Answer, Day, Solution};
+
+use crate::parse_commands;
+use crate::submarine::{Command, Model, SubmarineModel};
+
+/// Day registration used by the runners
+pub fn day() -> Day {
+    Day::new::<Dive>(2, "Dive!", "./day_2")
+}
+
+/// Dive puzzle solution
+pub struct Dive {
+    commands: Vec<Command>
+}
+
+impl Solution for Dive {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Dive { commands: parse_commands(input)? })
+    }
+
+    fn part_one(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_one_answer(&self.commands))
+    }
+
+    fn part_two(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_two_answer(&self.commands))
+    }
+}
+
+/// Final position of the submarine ignoring aim
+pub fn part_one_answer(commands: &[Command]) -> Answer {
+    model_answer(Model::Simple, commands)
+}
+
+/// Final position of the submarine using aim
+pub fn part_two_answer(commands: &[Command]) -> Answer {
+    model_answer(Model::Aim, commands)
+}
+
+fn model_answer(model: Model, commands: &[Command]) -> Answer {
+    let mut submarine = model.submarine();
+    submarine.process_commands(commands);
+
+    position_answer(submarine.as_ref())
+}
+
+/// Answer from the current position of a submarine
+pub fn position_answer(submarine: &dyn SubmarineModel) -> Answer {
+    let (position, depth) = (submarine.get_position(), submarine.get_depth());
+    Answer::new("depth_x_position", position * depth)
+        .with("position", position)
+        .with("depth", depth)
+}
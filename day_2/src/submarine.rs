@@ -1,30 +1,195 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Command {
     FORWARD(i32),
     DOWN(i32),
     UP(i32)
 }
 
-#[allow(dead_code)]
+/// Parse a command such as `forward 5`
+impl FromStr for Command {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let command: fn(i32) -> Command = match tokens.next().unwrap_or_default() {
+            "forward" => Command::FORWARD,
+            "down" => Command::DOWN,
+            "up" => Command::UP,
+            unk => return Err(CommandError::UnknownVerb(unk.to_string()))
+        };
+        let amount = tokens.next()
+            .ok_or(CommandError::MissingAmount)?
+            .parse()
+            .map_err(CommandError::InvalidAmount)?;
+
+        let extra: Vec<&str> = tokens.collect();
+        if !extra.is_empty() { return Err(CommandError::ExtraTokens(extra.join(" "))) }
+
+        Ok(command(amount))
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::FORWARD(x) => write!(f, "forward {}", x),
+            Command::DOWN(y) => write!(f, "down {}", y),
+            Command::UP(y) => write!(f, "up {}", y)
+        }
+    }
+}
+
+/// Reason a command could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// Movement other than `forward`, `down` or `up`
+    UnknownVerb(String),
+    MissingAmount,
+    InvalidAmount(ParseIntError),
+    /// Tokens following the amount
+    ExtraTokens(String)
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::UnknownVerb(verb) => write!(f, "unknown movement '{}'", verb),
+            CommandError::MissingAmount => write!(f, "missing movement amount"),
+            CommandError::InvalidAmount(e) => write!(f, "invalid movement amount: {}", e),
+            CommandError::ExtraTokens(tokens) => write!(f, "unexpected '{}' after amount", tokens)
+        }
+    }
+}
+
+impl Error for CommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CommandError::InvalidAmount(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+/// Position, depth and aim of a submarine at some point of its course
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubmarineState {
+    pub position: i32,
+    pub depth: i32,
+    /// Always 0 for models without aim
+    pub aim: i32
+}
+
+/// Movement model interpreting course commands
+pub trait SubmarineModel {
+    fn get_position(&self) -> i32;
+
+    fn get_depth(&self) -> i32;
+
+    fn state(&self) -> SubmarineState;
+
+    /// Move back to a state previously taken with [`SubmarineModel::state`]
+    fn restore(&mut self, state: SubmarineState);
+
+    fn process_command(&mut self, command: Command);
+
+    /// Process commands in order
+    fn process_commands(&mut self, commands: &[Command]) {
+        for command in commands {
+            self.process_command(*command);
+        }
+    }
+}
+
+impl<S: SubmarineModel + ?Sized> SubmarineModel for Box<S> {
+    fn get_position(&self) -> i32 {
+        self.as_ref().get_position()
+    }
+
+    fn get_depth(&self) -> i32 {
+        self.as_ref().get_depth()
+    }
+
+    fn state(&self) -> SubmarineState {
+        self.as_ref().state()
+    }
+
+    fn restore(&mut self, state: SubmarineState) {
+        self.as_mut().restore(state)
+    }
+
+    fn process_command(&mut self, command: Command) {
+        self.as_mut().process_command(command)
+    }
+}
+
+/// Available movement models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    /// Up and down change depth directly, see [`Submarine`]
+    Simple,
+    /// Up and down change aim, see [`AdvancedSubmarine`]
+    Aim
+}
+
+impl Model {
+    /// New submarine at the surface moving with this model
+    pub fn submarine(self) -> Box<dyn SubmarineModel> {
+        match self {
+            Model::Simple => Box::new(Submarine::new()),
+            Model::Aim => Box::new(AdvancedSubmarine::new())
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simple" => Ok(Model::Simple),
+            "aim" => Ok(Model::Aim),
+            unk => Err(format!("unknown submarine model '{}'", unk))
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct Submarine {
     position: i32,
     depth: i32
 }
 
-#[allow(dead_code)]
 impl Submarine {
     pub fn new() -> Submarine {
         Submarine { position: 0, depth: 0 }
     }
+}
 
-    pub fn get_position(&self) -> i32 {
+impl SubmarineModel for Submarine {
+    fn get_position(&self) -> i32 {
         self.position
     }
 
-    pub fn get_depth(&self) -> i32 {
+    fn get_depth(&self) -> i32 {
         self.depth
     }
 
-    pub fn process_command(&mut self, command: Command) {
+    fn state(&self) -> SubmarineState {
+        SubmarineState { position: self.position, depth: self.depth, aim: 0 }
+    }
+
+    fn restore(&mut self, state: SubmarineState) {
+        self.position = state.position;
+        self.depth = state.depth;
+    }
+
+    fn process_command(&mut self, command: Command) {
         match command {
             Command::DOWN(y) => self.depth += y,
             Command::UP(y) => self.depth -= y,
@@ -33,6 +198,7 @@ impl Submarine {
     }
 }
 
+#[derive(Default)]
 pub struct AdvancedSubmarine {
     position: i32,
     depth: i32,
@@ -43,16 +209,28 @@ impl AdvancedSubmarine {
     pub fn new() -> AdvancedSubmarine {
         AdvancedSubmarine { position: 0, depth: 0, aim: 0 }
     }
+}
 
-    pub fn get_position(&self) -> i32 {
+impl SubmarineModel for AdvancedSubmarine {
+    fn get_position(&self) -> i32 {
         self.position
     }
 
-    pub fn get_depth(&self) -> i32 {
+    fn get_depth(&self) -> i32 {
         self.depth
     }
 
-    pub fn process_command(&mut self, command: Command) {
+    fn state(&self) -> SubmarineState {
+        SubmarineState { position: self.position, depth: self.depth, aim: self.aim }
+    }
+
+    fn restore(&mut self, state: SubmarineState) {
+        self.position = state.position;
+        self.depth = state.depth;
+        self.aim = state.aim;
+    }
+
+    fn process_command(&mut self, command: Command) {
         match command {
             Command::DOWN(a) => self.aim += a,
             Command::UP(a) => self.aim -= a,
@@ -62,4 +240,45 @@ impl AdvancedSubmarine {
             }
         };
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::COURSE;
+    use super::{Command, CommandError, Model};
+
+    #[test]
+    fn should_move_each_model_from_same_commands() {
+        let mut simple = "simple".parse::<Model>().unwrap().submarine();
+        let mut aim = "aim".parse::<Model>().unwrap().submarine();
+
+        for command in COURSE {
+            simple.process_command(command);
+            aim.process_command(command);
+        }
+
+        assert_eq!((15, 10), (simple.get_position(), simple.get_depth()));
+        assert_eq!((15, 60), (aim.get_position(), aim.get_depth()));
+    }
+
+    #[test]
+    fn should_parse_commands() {
+        assert_eq!(Ok(Command::FORWARD(5)), "forward 5".parse());
+        assert_eq!(Ok(Command::DOWN(3)), " down  3 ".parse());
+        assert_eq!(Ok(Command::UP(1)), "up 1".parse());
+        assert_eq!("forward 5", Command::FORWARD(5).to_string());
+    }
+
+    #[test]
+    fn should_reject_malformed_commands() {
+        assert_eq!(Err(CommandError::UnknownVerb("back".to_string())), "back 5".parse::<Command>());
+        assert_eq!(Err(CommandError::MissingAmount), "forward".parse::<Command>());
+        assert!(matches!("up five".parse::<Command>(), Err(CommandError::InvalidAmount(_))));
+        assert_eq!(Err(CommandError::ExtraTokens("now please".to_string())), "down 2 now please".parse::<Command>());
+    }
+
+    #[test]
+    fn should_reject_unknown_model() {
+        assert_eq!(Err("unknown submarine model 'turbo'".to_string()), "turbo".parse::<Model>());
+    }
+}
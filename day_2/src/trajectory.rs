@@ -0,0 +1,197 @@
+use common::Format;
+
+use crate::submarine::{Command, SubmarineModel, SubmarineState};
+
+/// State of a submarine after each command of a course
+///
+/// States are numbered by step: step 0 is the start and step `n` the state after the `n`th command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    /// State before the first command
+    pub start: SubmarineState,
+    /// Commands processed with the state right after each of them
+    pub steps: Vec<(Command, SubmarineState)>
+}
+
+impl Trajectory {
+    /// Process `commands` recording the state of `submarine` after each of them
+    pub fn record<S, I>(submarine: &mut S, commands: I) -> Trajectory
+        where S: SubmarineModel + ?Sized, I: IntoIterator<Item = Command>
+    {
+        let start = submarine.state();
+        let steps = commands.into_iter()
+            .map(|command| {
+                submarine.process_command(command);
+                (command, submarine.state())
+            })
+            .collect();
+
+        Trajectory { start, steps }
+    }
+
+    /// Deepest depth with the first step reaching it
+    pub fn max_depth(&self) -> (usize, i32) {
+        self.states().fold((0, self.start.depth), |deepest, (step, state)| {
+            if state.depth > deepest.1 { (step, state.depth) } else { deepest }
+        })
+    }
+
+    /// First step deeper than `depth`
+    pub fn first_exceeding(&self, depth: i32) -> Option<usize> {
+        self.states()
+            .find(|(_, state)| state.depth > depth)
+            .map(|(step, _)| step)
+    }
+
+    /// Length of the straight moves between consecutive states
+    pub fn distance(&self) -> f64 {
+        let mut previous = self.start;
+        self.steps.iter()
+            .map(|(_, state)| {
+                let dx = (state.position - previous.position) as f64;
+                let dy = (state.depth - previous.depth) as f64;
+                previous = *state;
+                dx.hypot(dy)
+            })
+            .sum()
+    }
+
+    /// One row per step with the command leading to it, the start row has no command
+    pub fn to_csv(&self) -> String {
+        let start = format!("0,,{},{},{}", self.start.position, self.start.depth, self.start.aim);
+        let mut rows = vec!["step,command,position,depth,aim".to_string(), start];
+        for (idx, (command, state)) in self.steps.iter().enumerate() {
+            rows.push(format!("{},{},{},{},{}", idx + 1, command, state.position, state.depth, state.aim));
+        }
+
+        rows.join("\n")
+    }
+
+    /// Summary of the trajectory, optionally looking for the first step deeper than `exceeds`
+    pub fn summary(&self, exceeds: Option<i32>) -> TrajectorySummary {
+        TrajectorySummary {
+            commands: self.steps.len(),
+            max_depth: self.max_depth(),
+            distance: self.distance(),
+            exceeds: exceeds.map(|depth| (depth, self.first_exceeding(depth)))
+        }
+    }
+
+    /// States after each command with their step
+    fn states(&self) -> impl Iterator<Item = (usize, &SubmarineState)> {
+        self.steps.iter().map(|(_, state)| state).enumerate().map(|(idx, state)| (idx + 1, state))
+    }
+}
+
+/// Figures of a [`Trajectory`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectorySummary {
+    pub commands: usize,
+    /// First step reaching the deepest depth, with that depth
+    pub max_depth: (usize, i32),
+    pub distance: f64,
+    /// Depth looked for, with the first step going deeper if any
+    pub exceeds: Option<(i32, Option<usize>)>
+}
+
+impl TrajectorySummary {
+    pub fn render(&self, format: Format) -> String {
+        let (max_depth_step, max_depth) = self.max_depth;
+        match format {
+            Format::Text => {
+                let mut lines = vec![
+                    format!("Trajectory of {} commands", self.commands),
+                    format!("  Max depth: {} (step {})", max_depth, max_depth_step),
+                    format!("  Distance travelled: {:.2}", self.distance)
+                ];
+                match self.exceeds {
+                    Some((depth, Some(step))) => lines.push(format!("  First deeper than {}: step {}", depth, step)),
+                    Some((depth, None)) => lines.push(format!("  Never deeper than {}", depth)),
+                    None => ()
+                }
+                lines.join("\n")
+            },
+            Format::Json => {
+                let exceeds = match self.exceeds {
+                    Some((depth, step)) => format!(
+                        ",\"exceeds\":{},\"first_exceeding_step\":{}",
+                        depth,
+                        step.map_or("null".to_string(), |step| step.to_string())
+                    ),
+                    None => String::new()
+                };
+                format!(
+                    "{{\"commands\":{},\"max_depth\":{},\"max_depth_step\":{},\"distance\":{}{}}}",
+                    self.commands, max_depth, max_depth_step, self.distance, exceeds
+                )
+            },
+            Format::Tsv => {
+                let mut rows = vec![
+                    "statistic\tvalue".to_string(),
+                    format!("commands\t{}", self.commands),
+                    format!("max_depth\t{}", max_depth),
+                    format!("max_depth_step\t{}", max_depth_step),
+                    format!("distance\t{}", self.distance)
+                ];
+                if let Some((depth, step)) = self.exceeds {
+                    rows.push(format!("exceeds\t{}", depth));
+                    rows.push(format!("first_exceeding_step\t{}", step.map_or(String::new(), |step| step.to_string())));
+                }
+                rows.join("\n")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Format;
+
+    use crate::fixtures::COURSE;
+    use crate::submarine::{AdvancedSubmarine, Submarine};
+    use super::Trajectory;
+
+    #[test]
+    fn should_record_state_after_each_command() {
+        let trajectory = Trajectory::record(&mut AdvancedSubmarine::new(), COURSE);
+
+        let depths: Vec<i32> = trajectory.steps.iter().map(|(_, state)| state.depth).collect();
+        assert_eq!(vec![0, 0, 40, 40, 40, 60], depths);
+        assert_eq!(10, trajectory.steps[4].1.aim);
+        assert_eq!((6, 60), trajectory.max_depth());
+        assert_eq!(Some(3), trajectory.first_exceeding(0));
+        assert_eq!(None, trajectory.first_exceeding(60));
+    }
+
+    #[test]
+    fn should_measure_distance() {
+        let trajectory = Trajectory::record(&mut Submarine::new(), COURSE);
+
+        // Simple model moves along a single axis at a time
+        assert_eq!(31.0, trajectory.distance());
+        assert_eq!((5, 10), trajectory.max_depth());
+        assert_eq!((0, 0), Trajectory::record(&mut Submarine::new(), []).max_depth());
+    }
+
+    #[test]
+    fn should_export_csv() {
+        let trajectory = Trajectory::record(&mut AdvancedSubmarine::new(), COURSE[..3].iter().copied());
+
+        assert_eq!(
+            "step,command,position,depth,aim\n0,,0,0,0\n1,forward 5,5,0,0\n2,down 5,5,0,5\n3,forward 8,13,40,5",
+            trajectory.to_csv()
+        );
+    }
+
+    #[test]
+    fn should_render_summary() {
+        let summary = Trajectory::record(&mut Submarine::new(), COURSE).summary(Some(5));
+
+        assert_eq!(
+            "{\"commands\":6,\"max_depth\":10,\"max_depth_step\":5,\"distance\":31,\"exceeds\":5,\"first_exceeding_step\":5}",
+            summary.render(Format::Json)
+        );
+        assert!(summary.render(Format::Text).ends_with("First deeper than 5: step 5"));
+        assert!(summary.render(Format::Tsv).contains("\nmax_depth\t10\n"));
+    }
+}
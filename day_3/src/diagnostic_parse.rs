@@ -1,4 +1,8 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
 #[deprecated()]
+#[allow(dead_code)]
 pub fn parse_report_line(data: &str) -> u32 {
     let mut num: u32 = 0;
     for (idx, binary_item) in data.chars().rev().enumerate() {
@@ -6,15 +10,35 @@ pub fn parse_report_line(data: &str) -> u32 {
         num += binary_num << idx;
     }
 
-    return num;
+    num
+}
+
+/// Report line that is not a binary number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLine {
+    /// Line number, starting at 1
+    pub line: usize,
+    pub text: String
+}
+
+impl Display for InvalidLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: invalid binary number '{}'", self.line, self.text)
+    }
 }
 
+impl Error for InvalidLine {}
+
+/// Report rates, computed without keeping the processed lines
+///
+/// Memory is bounded by the line width whatever the number of lines: power consumption only needs zero
+/// counts by position, and life support rates are found in a trie counting lines by bit prefix.
+#[derive(Default)]
 pub struct DiagnosticReport {
     gamma_rate: u32,
     epsilon_rate: u32,
-    o2_rate: u32,
-    co2_rate: u32,
-    processed_lines: Vec<String>,
+    zeroes_count: Vec<u32>,
+    processed_lines: BitTrie,
     proc_lines_count: u32
 }
 
@@ -23,14 +47,14 @@ impl DiagnosticReport {
         DiagnosticReport {
             gamma_rate: 0,
             epsilon_rate: 0,
-            o2_rate: 0,
-            co2_rate: 0,
-            processed_lines: Vec::new(),
+            zeroes_count: Vec::new(),
+            processed_lines: BitTrie::default(),
             proc_lines_count: 0
         }
     }
 
-    pub fn from_data(data: Vec<String>) -> DiagnosticReport {
+    /// Build report from report lines, which can be lazily streamed
+    pub fn from_data<I: IntoIterator<Item = String>>(data: I) -> DiagnosticReport {
         let mut diagnostic_repo = DiagnosticReport::new();
         for line in data {
             diagnostic_repo.process_line(line);
@@ -51,59 +75,43 @@ impl DiagnosticReport {
         self.gamma_rate * self.epsilon_rate
     }
 
+    /// Life support rates are calculated on demand, as they need to filter all processed lines
     pub fn get_oxigen_rate(&self) -> u32 {
-        self.o2_rate
+        self.processed_lines.filter(most_common_bit)
     }
 
     pub fn get_co2_rate(&self) -> u32 {
-        self.co2_rate
+        self.processed_lines.filter(|zeroes, ones| most_common_bit(zeroes, ones) ^ 1)
     }
 
     pub fn get_life_support_rate(&self) -> u32 {
-        self.o2_rate * self.co2_rate
+        self.get_oxigen_rate() * self.get_co2_rate()
     }
 
-    pub fn process_line(&mut self, data: String) {
-        self.processed_lines.push(data);
-        self.proc_lines_count += 1;
-        self.calculate_rates();
-    }
-
-    fn calculate_rates(&mut self) {
-        self.calculate_power_consumption_rates();
-        self.calculate_life_support_rates();
-    }
-
-    fn calculate_life_support_rates(&mut self) {
-        self.o2_rate = self.calculate_o2_rate(&self.processed_lines, 0);
-        self.co2_rate = self.calculate_co2_rate(&self.processed_lines, 0);
-    }
-
-    fn calculate_o2_rate(&self, data: &Vec<String>, mut idx: usize) -> u32 {
-        if data.len() == 0 { return 0 }
-        if data.len() == 1 { return self.binary_string_to_dec_number(&data[0]) }
-
-        let filter_val = self.get_most_common_bit_in_idx(data, idx);
-        let filtered_data = self.filter_by_idx_number(data, idx, filter_val);
-        idx += 1;
+    /// Check and process report line number `line_n`, blank lines are skipped
+    pub fn process_report_line(&mut self, line_n: usize, line: &str) -> Result<(), InvalidLine> {
+        let line = line.trim();
+        if line.is_empty() { return Ok(()) }
+        if !line.chars().all(|c| c == '0' || c == '1') {
+            return Err(InvalidLine { line: line_n, text: line.to_string() });
+        }
 
-        self.calculate_o2_rate(&filtered_data, idx)
+        self.process_line(line.to_string());
+        Ok(())
     }
 
-    fn calculate_co2_rate(&self, data: &Vec<String>, mut idx: usize) -> u32 {
-        if data.len() == 0 { return 0 }
-        if data.len() == 1 { return self.binary_string_to_dec_number(&data[0]) }
-
-        let filter_val = self.get_most_common_bit_in_idx(data, idx);
-        let filtered_data = self.filter_by_idx_number(data, idx, filter_val ^ 1);
-        idx += 1;
-
-        self.calculate_co2_rate(&filtered_data, idx)
+    /// Process line updating power consumption rates incrementally
+    pub fn process_line(&mut self, data: String) {
+        self.add_zeroes_to_count(&data);
+        self.processed_lines.insert(&data);
+        self.proc_lines_count += 1;
+        self.calculate_power_consumption_rates();
     }
 
     #[deprecated]
-    fn calculate_life_support_rate(&self, data: &Vec<String>, mut idx: usize, filter_val: u32) -> u32 {
-        if data.len() == 0 { return 0 }
+    #[allow(dead_code)]
+    fn calculate_life_support_rate(&self, data: &[String], mut idx: usize, filter_val: u32) -> u32 {
+        if data.is_empty() { return 0 }
         if data.len() == 1 { return self.binary_string_to_dec_number(&data[0]) }
 
         let filtered_data = self.filter_by_idx_number(data, idx, filter_val);
@@ -112,7 +120,7 @@ impl DiagnosticReport {
         self.calculate_life_support_rate(&filtered_data, idx, filter_val)
     }
 
-    fn filter_by_idx_number(&self, data: &Vec<String>, idx: usize, filter_val: u32) -> Vec<String> {
+    fn filter_by_idx_number(&self, data: &[String], idx: usize, filter_val: u32) -> Vec<String> {
         let mut filtered_data: Vec<String> = Vec::new();
         for it in data.iter() {
             let bit = self.get_bit_at_position(it, idx);
@@ -124,23 +132,10 @@ impl DiagnosticReport {
         filtered_data
     }
 
-    fn get_most_common_bit_in_idx(&self, data: &Vec<String>, idx: usize) -> u32 {
-        let mut zero_count = 0;
-        for it in data.iter() {
-            let bit = self.get_bit_at_position(it, idx);
-            if bit == 0 { zero_count += 1 }
-        }
-
-        let zero_ratio = (zero_count as f64) / data.len() as f64;
-        if zero_ratio > 0.5 { 0 }
-        else { 1 }
-    }
-
     fn calculate_power_consumption_rates(&mut self) {
-        let zeroes_count = self.calculate_data_zeroes_count();
         let mut gamma_rate: u32 = 0;
         let mut epsilon_rate: u32 = 0;
-        for (idx, count) in zeroes_count.iter().enumerate() {
+        for (idx, count) in self.zeroes_count.iter().enumerate() {
             let zero_ratio = (*count as f64) / self.proc_lines_count as f64;
             let most_common = if zero_ratio > 0.5 { 0 } else { 1 };
             let least_common = most_common ^ 1;
@@ -153,32 +148,24 @@ impl DiagnosticReport {
         self.gamma_rate = gamma_rate;
     }
 
-    fn calculate_data_zeroes_count(&mut self) -> Vec<u32> {
-        let mut zeroes_count: Vec<u32> = Vec::new();
-        for line in self.processed_lines.iter() {
-            self.add_zeroes_to_count(&line, &mut zeroes_count);
-        }
-
-        zeroes_count
-    }
-
-    fn add_zeroes_to_count(&self, data: &str, zeroes_count: &mut Vec<u32>) {
-        self.adjust_zeroes_count_size(zeroes_count, &data.len());
+    fn add_zeroes_to_count(&mut self, data: &str) {
+        self.adjust_zeroes_count_size(&data.len());
         for (idx, binary_item) in data.chars().rev().enumerate() {
             let bit = binary_item.to_digit(2).unwrap();
             if bit == 0 {
-                zeroes_count[idx] += 1;
+                self.zeroes_count[idx] += 1;
             }
         }
     }
 
-    fn adjust_zeroes_count_size(&self, zeroes_count: &mut Vec<u32>, size: &usize) {
-        if zeroes_count.len() < *size {
-            zeroes_count.extend(vec![0; *size - zeroes_count.len()]);
+    fn adjust_zeroes_count_size(&mut self, size: &usize) {
+        if self.zeroes_count.len() < *size {
+            let missing = *size - self.zeroes_count.len();
+            self.zeroes_count.extend(vec![0; missing]);
         }
     }
 
-    fn get_bit_at_position(&self, binary_num: &String, idx: usize) -> u32 {
+    fn get_bit_at_position(&self, binary_num: &str, idx: usize) -> u32 {
         binary_num.chars()
             .nth(idx)
             .unwrap()
@@ -186,7 +173,7 @@ impl DiagnosticReport {
             .unwrap()
     }
 
-    fn binary_string_to_dec_number(&self, binary_num: &String) -> u32 {
+    fn binary_string_to_dec_number(&self, binary_num: &str) -> u32 {
         let mut num = 0;
         for (idx, c) in binary_num.chars().rev().enumerate() {
             num += c.to_digit(2).unwrap() << idx;
@@ -196,9 +183,88 @@ impl DiagnosticReport {
     }
 }
 
+/// Bit kept by the oxygen rate filter, 1 when both bits are as common
+fn most_common_bit(zeroes: u32, ones: u32) -> u32 {
+    if zeroes > ones { 0 } else { 1 }
+}
+
+/// Number of processed lines sharing each bit prefix
+#[derive(Default)]
+struct BitTrie {
+    /// First node is the root once a line was inserted
+    nodes: Vec<TrieNode>
+}
+
+#[derive(Default)]
+struct TrieNode {
+    count: u32,
+    /// Child node index by bit
+    children: [Option<usize>; 2]
+}
+
+impl BitTrie {
+    fn insert(&mut self, data: &str) {
+        if self.nodes.is_empty() { self.nodes.push(TrieNode::default()) }
+
+        let mut node = 0;
+        self.nodes[node].count += 1;
+        for binary_item in data.chars() {
+            let bit = binary_item.to_digit(2).unwrap() as usize;
+            node = match self.nodes[node].children[bit] {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children[bit] = Some(child);
+                    child
+                }
+            };
+            self.nodes[node].count += 1;
+        }
+    }
+
+    /// Value of the line left after keeping, position by position, the lines with the bit picked by
+    /// `select` from the zero and one counts, 0 when no line is left
+    fn filter<F: Fn(u32, u32) -> u32>(&self, select: F) -> u32 {
+        if self.nodes.is_empty() { return 0 }
+
+        let mut node = &self.nodes[0];
+        let mut num = 0;
+        loop {
+            let count = |bit: usize| node.children[bit].map_or(0, |child| self.nodes[child].count);
+            let bit = match (node.count, count(0), count(1)) {
+                // Remaining lines end here
+                (_, 0, 0) => return num,
+                // Single line left, follow it to its end
+                (1, zeroes, _) => if zeroes == 1 { 0 } else { 1 },
+                (_, zeroes, ones) => select(zeroes, ones) as usize
+            };
+
+            match node.children[bit] {
+                Some(child) => node = &self.nodes[child],
+                None => return 0
+            }
+            num = (num << 1) + bit as u32;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::DiagnosticReport;
+    use super::{DiagnosticReport, InvalidLine};
+
+    #[test]
+    fn should_reject_invalid_line() {
+        let mut diagnostic_report = DiagnosticReport::new();
+
+        assert_eq!(Ok(()), diagnostic_report.process_report_line(1, "101"));
+        assert_eq!(Ok(()), diagnostic_report.process_report_line(2, "  "));
+        assert_eq!(
+            Err(InvalidLine { line: 3, text: "1x1".to_string() }),
+            diagnostic_report.process_report_line(3, "1x1")
+        );
+        assert_eq!(5, diagnostic_report.get_gamma_rate());
+    }
 
     #[test]
     fn should_process_diagnostic_gamma_and_epsilon() {
@@ -232,6 +298,43 @@ mod tests {
         assert_eq!(diagnostic_report.get_power_consumption(), 11286);
     }
 
+    #[test]
+    fn should_update_rates_while_streaming_lines() {
+        let mut diagnostic_report = DiagnosticReport::new();
+        diagnostic_report.process_line("110".to_string());
+        diagnostic_report.process_line("100".to_string());
+
+        assert_eq!(diagnostic_report.get_gamma_rate(), 6); // 110
+        assert_eq!(diagnostic_report.get_epsilon_rate(), 1); // 001
+
+        diagnostic_report.process_line("000".to_string());
+        diagnostic_report.process_line("001".to_string());
+
+        assert_eq!(diagnostic_report.get_gamma_rate(), 4); // 100
+        assert_eq!(diagnostic_report.get_epsilon_rate(), 3); // 011
+    }
+
+    #[test]
+    fn should_not_grow_with_repeated_lines() {
+        let mut diagnostic_report = DiagnosticReport::from_data(get_test_report_data());
+        let nodes = diagnostic_report.processed_lines.nodes.len();
+
+        for _ in 0..100 {
+            get_test_report_data().into_iter().for_each(|line| diagnostic_report.process_line(line));
+        }
+
+        assert_eq!(nodes, diagnostic_report.processed_lines.nodes.len());
+        assert_eq!(diagnostic_report.get_oxigen_rate(), 23);
+    }
+
+    #[test]
+    fn should_process_streamed_report_data() {
+        let diagnostic_report = DiagnosticReport::from_data(get_test_report_data().into_iter().rev());
+
+        assert_eq!(diagnostic_report.get_power_consumption(), 198);
+        assert_eq!(diagnostic_report.get_life_support_rate(), 230);
+    }
+
     fn get_test_report_data() -> Vec<String> {
         vec![
             "00100".to_string(),
@@ -0,0 +1,4 @@
+pub mod diagnostic_parse;
+mod solution;
+
+pub use solution::{BinaryDiagnostic, day, part_one_answer, part_two_answer};
@@ -1,31 +1,50 @@
-mod diagnostic_parse;
+use std::process;
 
-use common::{FileConfig, get_input_file_path, load_data};
-
-use diagnostic_parse::{DiagnosticReport, parse_report_line};
+use common::{Cli, DayReport, FileConfig, Part, load_data};
+use day_3::diagnostic_parse::DiagnosticReport;
+use day_3::{part_one_answer, part_two_answer};
+#[allow(deprecated)]
+use day_3::diagnostic_parse::parse_report_line;
 
 fn main() {
+    let options = Cli::new("day_3").parse_env();
     let config = FileConfig::default("./day_3");
-    let report_data = load_data(get_input_file_path(&config));
+    let day = day_3::day();
+    options.run_shared_modes(&day, &config);
+    let report_data = match options.lines(&config) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut diagnostic_report = DiagnosticReport::new();
+    for (idx, line) in report_data.enumerate() {
+        let line = line.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        });
+        if let Err(e) = diagnostic_report.process_report_line(idx + 1, &line) {
+            eprintln!("{}: {}", options.input_name(&config), e);
+            process::exit(1);
+        }
+    }
 
-    let diagnostic_report = DiagnosticReport::from_data(report_data);
+    let mut report = DayReport::new(&day);
+    if options.runs(Part::One) {
+        report.add(Part::One, part_one_answer(&diagnostic_report));
+    }
 
-    println!(
-        "Gamma: {}\nEpsilon: {}\nPower consumption: {}\n",
-        diagnostic_report.get_gamma_rate(),
-        diagnostic_report.get_epsilon_rate(),
-        diagnostic_report.get_power_consumption()
-    );
+    if options.runs(Part::Two) {
+        report.add(Part::Two, part_two_answer(&diagnostic_report));
+    }
 
-    println!(
-        "O2: {}\nCO2 {}\nLife support: {}\n",
-        diagnostic_report.get_oxigen_rate(),
-        diagnostic_report.get_co2_rate(),
-        diagnostic_report.get_life_support_rate()
-    );
+    options.print_report(&report);
 }
 
 #[deprecated()]
+#[allow(dead_code, deprecated)]
 fn load_report_data(path: &str) -> Vec<u32> {
     load_data(path).into_iter()
         .map(|i| parse_report_line(&i))
@@ -0,0 +1,48 @@
+use std::error::Error;
+
+use common::{Answer, Day, Solution};
+
+use crate::diagnostic_parse::DiagnosticReport;
+
+/// Day registration used by the runners
+pub fn day() -> Day {
+    Day::new::<BinaryDiagnostic>(3, "Binary Diagnostic", "./day_3")
+}
+
+/// Binary diagnostic puzzle solution
+pub struct BinaryDiagnostic {
+    report: DiagnosticReport
+}
+
+impl Solution for BinaryDiagnostic {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let mut report = DiagnosticReport::new();
+        for (idx, line) in input.lines().enumerate() {
+            report.process_report_line(idx + 1, line)?;
+        }
+
+        Ok(BinaryDiagnostic { report })
+    }
+
+    fn part_one(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_one_answer(&self.report))
+    }
+
+    fn part_two(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_two_answer(&self.report))
+    }
+}
+
+/// Power consumption with the gamma and epsilon rates it is computed from
+pub fn part_one_answer(report: &DiagnosticReport) -> Answer {
+    Answer::new("power_consumption", report.get_power_consumption())
+        .with("gamma_rate", report.get_gamma_rate())
+        .with("epsilon_rate", report.get_epsilon_rate())
+}
+
+/// Life support rating with the oxygen and CO2 rates it is computed from
+pub fn part_two_answer(report: &DiagnosticReport) -> Answer {
+    Answer::new("life_support_rating", report.get_life_support_rate())
+        .with("oxygen_rate", report.get_oxigen_rate())
+        .with("co2_rate", report.get_co2_rate())
+}
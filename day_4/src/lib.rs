@@ -1,5 +1,28 @@
+use std::num::ParseIntError;
+
+use common::{Block, Grid, GridError};
+
+mod solution;
+
+pub use solution::{GiantSquid, day, parse_bingo, part_one_answer, part_two_answer};
+
 pub struct BingoCard {
-    rows: Vec<Vec<(u32, bool)>>
+    rows: Grid<(u32, bool)>
+}
+
+/// Winning card state when it completed its first line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Winner {
+    /// Sum of unmarked card numbers
+    pub unmarked: u32,
+    /// Number that completed the line
+    pub last_num: u32
+}
+
+impl Winner {
+    pub fn score(&self) -> u32 {
+        self.unmarked * self.last_num
+    }
 }
 
 /// Prints score of winning card
@@ -57,25 +80,71 @@ pub fn last_card_score(sequence: Vec<u32>, bingo_match: &mut BingoMatch) {
     };
 }
 
+/// First winning card, `None` if no card wins
+pub fn first_winner(sequence: &[u32], bingo_match: &mut BingoMatch) -> Option<Winner> {
+    for num in sequence {
+        let winners = bingo_match.mark(*num);
+        if let Some(card) = winners.first() {
+            return Some(Winner { unmarked: card.sum_unmarked(), last_num: *num });
+        }
+    }
+
+    None
+}
+
+/// Last card completing its first line, `None` if no card wins
+pub fn last_winner(sequence: &[u32], bingo_match: &mut BingoMatch) -> Option<Winner> {
+    let mut last_winner: Option<Winner> = None;
+    for num in sequence {
+        bingo_match.mark(*num).into_iter()
+            .filter(|card| card.count_completed_lines() == 1)
+            .for_each(|card| last_winner = Some(Winner { unmarked: card.sum_unmarked(), last_num: *num }));
+    }
+
+    last_winner
+}
+
+/// Score of first winning card, `None` if no card wins
+pub fn first_winner_score(sequence: &[u32], bingo_match: &mut BingoMatch) -> Option<u32> {
+    first_winner(sequence, bingo_match).map(|w| w.score())
+}
+
+/// Score of last card completing its first line, `None` if no card wins
+pub fn last_winner_score(sequence: &[u32], bingo_match: &mut BingoMatch) -> Option<u32> {
+    last_winner(sequence, bingo_match).map(|w| w.score())
+}
+
+/// Parse comma separated bingo number sequence
+pub fn parse_sequence(block: &Block) -> Result<Vec<u32>, ParseIntError> {
+    block.text().split(',')
+        .map(|it| it.trim().parse::<u32>())
+        .collect()
+}
+
+/// Parse whitespace separated bingo card rows
+pub fn parse_card(block: &Block) -> Result<BingoCard, GridError<ParseIntError>> {
+    Grid::parse_whitespace(&block.text()).map(|grid| BingoCard::from_grid(&grid))
+}
+
 impl BingoCard {
+    /// Create card from rows, panics if rows have different lengths
     pub fn from(rows: Vec<Vec<u32>>) -> BingoCard {
-        let rows = rows.iter()
-            .map(|r| {
-                r.iter()
-                    .map(|i| (*i, false))
-                    .collect()
-            })
-            .collect();
+        let grid = Grid::from_rows(rows)
+            .expect("Bingo card rows must have the same length");
+
+        BingoCard::from_grid(&grid)
+    }
 
-        BingoCard { rows }
+    pub fn from_grid(grid: &Grid<u32>) -> BingoCard {
+        BingoCard { rows: grid.map(|i| (*i, false)) }
     }
 
     /// Get row values
     ///
     /// # Arguments
     /// * `row_n` - Row index starting by 0
-    pub fn get_row(&self, row_n: usize) -> Option<&Vec<(u32, bool)>> {
-        self.rows.get(row_n)
+    pub fn get_row(&self, row_n: usize) -> Option<&[(u32, bool)]> {
+        self.rows.row(row_n)
     }
 
     /// Get row values
@@ -84,101 +153,72 @@ impl BingoCard {
     /// * `row_n` - Row index starting by 0
     /// * `col_n` - Col index starting by 0
     pub fn get_value(&self, row_n: usize, col_n: usize) -> Option<&(u32, bool)> {
-        self.rows.get(row_n).and_then(|row| {
-            row.get(col_n)
-        })
+        self.rows.get(row_n, col_n)
     }
 
     pub fn mark(&mut self, num: u32) -> bool {
-        let mut mod_row: Option<usize> = None;
-        let mut mod_col: Option<usize> = None;
-        self.rows.iter_mut().enumerate().for_each(|(i, row)| {
-            row.iter_mut().enumerate().for_each(|(j, it)| {
-                let (row_num, marked) = it;
-
-                if *row_num == num {
-                    *marked = true;
-                    mod_row = Some(i);
-                    mod_col = Some(j);
-                }
-            })
+        let mut mod_pos: Option<(usize, usize)> = None;
+        self.rows.iter_mut().for_each(|(pos, it)| {
+            let (row_num, marked) = it;
+
+            if *row_num == num {
+                *marked = true;
+                mod_pos = Some(pos);
+            }
         });
 
-        match mod_row {
-            Some(i) => self.is_row_completed(i)
-                || self.is_col_completed(mod_col.unwrap()),
+        match mod_pos {
+            Some((i, j)) => self.is_row_completed(i)
+                || self.is_col_completed(j),
             None => false
         }
     }
 
     pub fn sum_unmarked(&self) -> u32 {
-        self.rows.iter().map(|row| {
-            row.iter()
-                .filter(|(_, marked)| !*marked)
-                .fold(0, |acc, (val, _)| acc + val)
-        })
-            .reduce(|acc, val| acc + val)
-            .unwrap_or(0)
+        self.rows.iter()
+            .filter(|(_, (_, marked))| !*marked)
+            .fold(0, |acc, (_, (val, _))| acc + val)
     }
 
     /// Counts completed rows and columns
     pub fn count_completed_lines(&self) -> u32 {
-        if self.rows.len() == 0 || self.rows[0].len() == 0 { return 0}
-
-        let mut col_status = vec![true;self.rows[0].len()];
-
-        let count: u32 = self.rows.iter().map(|row| {
-            row.iter().enumerate().fold(true, |agg, (i, it)| {
-                let (_, marked) = it;
-                col_status[i] &= *marked;
-                agg && *marked
-            })
-        })
-            .fold(0, |agg, row_res| agg + row_res as u32);
-
-        count + col_status.iter()
-            .fold(0, |agg, completed| agg + *completed as u32)
+        let completed_rows = self.rows.rows()
+            .map(|row| row.iter().all(|(_, marked)| *marked))
+            .filter(|completed| *completed)
+            .count();
+        let completed_cols = self.rows.columns()
+            .map(|mut col| col.all(|(_, marked)| *marked))
+            .filter(|completed| *completed)
+            .count();
+
+        (completed_rows + completed_cols) as u32
     }
 
     fn is_row_completed(&self, row_n: usize) -> bool {
-        let row =  self.rows.get(row_n);
-        if row.is_none() { return false }
-
-        row.unwrap().iter()
-            .map(|it| {
-                let (_, marked) = it;
-                marked
-            })
-            .fold(true, |acc, mk | acc && *mk)
+        self.rows.row(row_n)
+            .is_some_and(|row| row.iter().all(|(_, marked)| *marked))
     }
 
     fn is_col_completed(&self, col_n: usize) -> bool {
-        self.rows.iter().all(|row| {
-            let it = row.get(col_n);
-            if it.is_none() { return false }
-
-            it.unwrap().1
-        })
+        self.rows.column(col_n)
+            .is_some_and(|mut col| col.all(|(_, marked)| *marked))
     }
 }
 
 impl Clone for BingoCard {
     fn clone(&self) -> Self {
-        let rows: Vec<Vec<(u32, bool)>> = self.rows.iter()
-            .map(|row| row.clone())
-            .collect();
-
-        BingoCard { rows }
+        BingoCard { rows: self.rows.clone() }
     }
 }
 
+#[derive(Clone)]
 pub struct BingoMatch {
     cards: Vec<BingoCard>
 }
 
 impl BingoMatch {
     pub fn new(cards: Vec<BingoCard>) -> BingoMatch {
-        return BingoMatch { cards }
+        BingoMatch { cards }
     }
 
     pub fn mark(&mut self, num: u32) -> Vec<&BingoCard> {
@@ -195,8 +235,9 @@ impl BingoMatch {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
-    use crate::BingoMatch;
+    use crate::{BingoMatch, first_winner_score, last_winner_score};
     use super::BingoCard;
 
     #[test]
@@ -273,6 +314,18 @@ mod tests {
         assert_eq!(72, cards[0].sum_unmarked());
     }
 
+    #[test]
+    fn should_return_first_and_last_winner_scores() {
+        let bingo_match = BingoMatch::new(vec![
+            create_bingo_card_w_plus(0),
+            create_bingo_card_w_plus(9)
+        ]);
+        let sequence = vec![11, 12, 13, 21, 22, 10];
+
+        assert_eq!(Some(72 * 13), first_winner_score(&sequence, &mut bingo_match.clone()));
+        assert_eq!(Some(113 * 10), last_winner_score(&sequence, &mut bingo_match.clone()));
+    }
+
     fn create_bingo_card() -> BingoCard {
         create_bingo_card_w_plus(0)
     }
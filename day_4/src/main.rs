@@ -1,42 +1,36 @@
-use common::{FileConfig, get_input_file_path, load_data};
-use day_4::{BingoCard, BingoMatch, last_card_score, play_match};
+use std::error::Error;
+use std::process;
+
+use common::{Cli, DayReport, FileConfig, Options, Part};
+use day_4::{BingoMatch, parse_bingo, part_one_answer, part_two_answer};
 
 fn main() {
+    let options = Cli::new("day_4").parse_env();
     let config = FileConfig::default("./day_4");
-    let (sequence, cards) = load_bingo_data(get_input_file_path(&config));
-
-    let mut bingo_match = BingoMatch::new(cards);
-    // play_match(sequence, &mut bingo_match);
-    last_card_score(sequence, &mut bingo_match);
-}
-
-fn load_bingo_data(path: &str) -> (Vec<u32>, Vec<BingoCard>) {
-    let data = load_data(path);
-
-    // First line is bingo number sequence
-    let mark_sequence: Vec<u32> = data[0].split(",").into_iter()
-        .map(|it| it.trim().parse::<u32>().unwrap())
-        .collect();
-
-    // Next lines are cards data
-    let mut cards = Vec::new();
-    let mut current_rows = Vec::new();
-    for line in &data[2..] {
-        if line.trim().is_empty() {
-            cards.push(BingoCard::from(current_rows));
-            current_rows = Vec::new();
-            continue;
+    let day = day_4::day();
+    options.run_shared_modes(&day, &config);
+    let (sequence, bingo_match) = match load_bingo_data(&options, &config) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
+    };
 
-        let row: Vec<u32> = line.split_whitespace().into_iter()
-            .map(|it| it.trim().parse::<u32>().unwrap())
-            .collect();
+    let mut report = DayReport::new(&day);
+    if options.runs(Part::One) {
+        report.add(Part::One, part_one_answer(&sequence, &bingo_match));
+    }
 
-        current_rows.push(row);
+    if options.runs(Part::Two) {
+        report.add(Part::Two, part_two_answer(&sequence, &bingo_match));
     }
 
-    // If file do not end with additional linebreak last card will not be added in iteration
-    if !current_rows.is_empty() { cards.push(BingoCard::from(current_rows)) }
+    options.print_report(&report);
+}
 
-    (mark_sequence, cards)
-}
\ No newline at end of file
+fn load_bingo_data(options: &Options, config: &FileConfig) -> Result<(Vec<u32>, BingoMatch), Box<dyn Error>> {
+    let lines = options.lines(config)?.collect::<Result<Vec<String>, _>>()?;
+
+    parse_bingo(options.input_name(config), lines)
+}
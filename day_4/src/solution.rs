@@ -0,0 +1,73 @@
+use std::error::Error;
+
+use common::{Answer, Day, Solution, load_data_from_str, split_blocks};
+
+use crate::{BingoMatch, Winner, first_winner, last_winner, parse_card, parse_sequence};
+
+const INPUT_NAME: &str = "<input>";
+
+/// Day registration used by the runners
+pub fn day() -> Day {
+    Day::new::<GiantSquid>(4, "Giant Squid", "./day_4")
+}
+
+/// Giant squid puzzle solution
+pub struct GiantSquid {
+    sequence: Vec<u32>,
+    bingo_match: BingoMatch
+}
+
+impl Solution for GiantSquid {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let (sequence, bingo_match) = parse_bingo(INPUT_NAME, load_data_from_str(input))?;
+
+        Ok(GiantSquid { sequence, bingo_match })
+    }
+
+    fn part_one(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_one_answer(&self.sequence, &self.bingo_match))
+    }
+
+    fn part_two(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_two_answer(&self.sequence, &self.bingo_match))
+    }
+}
+
+/// Parse the bingo number sequence and the cards following it, errors are reported against `input_name`
+pub fn parse_bingo<I>(input_name: &str, lines: I) -> Result<(Vec<u32>, BingoMatch), Box<dyn Error>>
+    where I: IntoIterator<Item = String>
+{
+    let mut blocks = split_blocks(lines);
+
+    // First block is bingo number sequence
+    let sequence = match blocks.next() {
+        Some(header) => header.parse_with(input_name, parse_sequence)?,
+        None => return Err(format!("{}: missing bingo number sequence", input_name).into())
+    };
+
+    // Next blocks are cards data
+    let cards = blocks
+        .map(|block| block.parse_with(input_name, parse_card))
+        .collect::<Result<_, _>>()?;
+
+    Ok((sequence, BingoMatch::new(cards)))
+}
+
+/// Score of the first winning card
+pub fn part_one_answer(sequence: &[u32], bingo_match: &BingoMatch) -> Answer {
+    score_answer(first_winner(sequence, &mut bingo_match.clone()))
+}
+
+/// Score of the last winning card
+pub fn part_two_answer(sequence: &[u32], bingo_match: &BingoMatch) -> Answer {
+    score_answer(last_winner(sequence, &mut bingo_match.clone()))
+}
+
+fn score_answer(winner: Option<Winner>) -> Answer {
+    match winner {
+        Some(winner) => Answer::new("score", winner.score())
+            .with("unmarked", winner.unmarked)
+            .with("last_num", winner.last_num),
+        None => Answer::new("score", "No winner in match")
+    }
+}